@@ -0,0 +1,33 @@
+use actix_web::{get, web, App, HttpServer};
+use user_agent_parser::{Device, Engine, Product, UserAgent, UserAgentParser, CPU, OS};
+
+#[get("/")]
+async fn index(
+    user_agent: UserAgent<'static>,
+    product: Product<'static>,
+    os: OS<'static>,
+    device: Device<'static>,
+    cpu: CPU<'static>,
+    engine: Engine<'static>,
+) -> String {
+    format!(
+        "{user_agent:#?}\n{product:#?}\n{os:#?}\n{device:#?}\n{cpu:#?}\n{engine:#?}",
+        user_agent = user_agent,
+        product = product,
+        os = os,
+        device = device,
+        cpu = cpu,
+        engine = engine,
+    )
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let user_agent_parser =
+        web::Data::new(UserAgentParser::from_path("uap-core/regexes.yaml").unwrap());
+
+    HttpServer::new(move || App::new().app_data(user_agent_parser.clone()).service(index))
+        .bind(("127.0.0.1", 8000))?
+        .run()
+        .await
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use user_agent_parser::{Device, Engine, Product, UserAgent, UserAgentParser, CPU, OS};
+
+async fn index(
+    user_agent: UserAgent<'static>,
+    product: Product<'static>,
+    os: OS<'static>,
+    device: Device<'static>,
+    cpu: CPU<'static>,
+    engine: Engine<'static>,
+) -> String {
+    format!(
+        "{user_agent:#?}\n{product:#?}\n{os:#?}\n{device:#?}\n{cpu:#?}\n{engine:#?}",
+        user_agent = user_agent,
+        product = product,
+        os = os,
+        device = device,
+        cpu = cpu,
+        engine = engine,
+    )
+}
+
+#[tokio::main]
+async fn main() {
+    let user_agent_parser = Arc::new(UserAgentParser::from_path("uap-core/regexes.yaml").unwrap());
+
+    let app = Router::new().route("/", get(index)).with_state(user_agent_parser);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await.unwrap();
+
+    axum::serve(listener, app).await.unwrap();
+}
@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate rocket;
 
-use user_agent_parser::{Device, Engine, Product, UserAgent, UserAgentParser, CPU, OS};
+use user_agent_parser::{Device, Engine, Product, UserAgent, UserAgentParserFairing, CPU, OS};
 
 #[get("/")]
 fn index(
@@ -25,7 +25,8 @@ fn index(
 
 #[launch]
 fn rocket() -> _ {
-    rocket::build()
-        .manage(UserAgentParser::from_path("uap-core/regexes.yaml").unwrap())
-        .mount("/", routes![index])
+    let figment =
+        rocket::Config::figment().merge(("user_agent_parser.path", "uap-core/regexes.yaml"));
+
+    rocket::custom(figment).attach(UserAgentParserFairing).mount("/", routes![index])
 }
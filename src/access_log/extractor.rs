@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+
+use serde_json::Value;
+
+/// The format of an access log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogFormat {
+    /// The nginx/Apache combined log format. The user agent is the third quoted field, after the request line and the referer. Extra fields may follow it.
+    Combined,
+    /// The IIS W3C extended log format. The user agent is the `cs(User-Agent)` field declared by the latest `#Fields:` directive.
+    W3C,
+    /// One JSON object per line. The user agent is the string value of the given top-level key.
+    JsonLines(String),
+}
+
+/// A line of an access log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogLine<'l> {
+    /// A blank line, a directive, or a line which cannot be recognized.
+    Skipped,
+    /// A request. The user agent is `None` if it is absent, empty or `-`.
+    Request(Option<Cow<'l, str>>),
+}
+
+/// Extracts the user agents from the lines of an access log.
+///
+/// Use one extractor per log file because the W3C extended format is stateful.
+#[derive(Debug, Clone)]
+pub struct UserAgentExtractor {
+    format:            LogFormat,
+    w3c_field_indexes: Option<(usize, usize)>,
+}
+
+impl UserAgentExtractor {
+    #[inline]
+    pub fn new(format: LogFormat) -> UserAgentExtractor {
+        UserAgentExtractor {
+            format,
+            w3c_field_indexes: None,
+        }
+    }
+
+    /// Extract the user agent from a line.
+    pub fn extract<'l>(&mut self, line: &'l str) -> LogLine<'l> {
+        let line = line.trim_end_matches(['\r', '\n']);
+
+        if line.trim().is_empty() {
+            return LogLine::Skipped;
+        }
+
+        let user_agent = match &self.format {
+            LogFormat::Combined => match quoted_fields(line).nth(2) {
+                Some(user_agent) => user_agent,
+                None => return LogLine::Skipped,
+            },
+            LogFormat::W3C => {
+                if let Some(directive) = line.strip_prefix('#') {
+                    if let Some(fields) = directive.strip_prefix("Fields:") {
+                        self.w3c_field_indexes = fields
+                            .split_whitespace()
+                            .position(|field| field.eq_ignore_ascii_case("cs(User-Agent)"))
+                            .map(|index| (index, fields.split_whitespace().count()));
+                    }
+
+                    return LogLine::Skipped;
+                }
+
+                let (index, count) = match self.w3c_field_indexes {
+                    Some(indexes) => indexes,
+                    None => return LogLine::Skipped,
+                };
+
+                let values: Vec<&str> = line.split_whitespace().collect();
+
+                if values.len() != count {
+                    return LogLine::Skipped;
+                }
+
+                // spaces in the user agent are written as `+`
+                let user_agent = values[index];
+
+                if user_agent.contains('+') {
+                    Cow::from(user_agent.replace('+', " "))
+                } else {
+                    Cow::from(user_agent)
+                }
+            },
+            LogFormat::JsonLines(key) => match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(mut object)) => match object.remove(key) {
+                    Some(Value::String(user_agent)) => Cow::from(user_agent),
+                    _ => return LogLine::Request(None),
+                },
+                _ => return LogLine::Skipped,
+            },
+        };
+
+        let trimmed_user_agent = user_agent.trim();
+
+        if trimmed_user_agent.is_empty() || trimmed_user_agent == "-" {
+            LogLine::Request(None)
+        } else if trimmed_user_agent.len() == user_agent.len() {
+            LogLine::Request(Some(user_agent))
+        } else {
+            LogLine::Request(Some(Cow::from(trimmed_user_agent.to_string())))
+        }
+    }
+}
+
+/// Iterate the double-quoted fields of a line. Escapes (`\"`, `\\` and `\xHH`) are decoded.
+fn quoted_fields(line: &str) -> impl Iterator<Item = Cow<'_, str>> {
+    let mut rest = line;
+
+    std::iter::from_fn(move || {
+        let start = rest.find('"')? + 1;
+        let bytes = rest.as_bytes();
+
+        let mut end = start;
+        let mut escaped = false;
+
+        while end < bytes.len() {
+            match bytes[end] {
+                b'\\' => {
+                    escaped = true;
+                    end += 2;
+                },
+                b'"' => break,
+                _ => end += 1,
+            }
+        }
+
+        let end = end.min(bytes.len());
+        let field = &rest[start..end];
+
+        rest = rest.get(end + 1..).unwrap_or_default();
+
+        if escaped {
+            Some(Cow::from(unescape(field)))
+        } else {
+            Some(Cow::from(field))
+        }
+    })
+}
+
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+
+    let mut p = 0;
+
+    while p < bytes.len() {
+        if bytes[p] == b'\\' && p + 1 < bytes.len() {
+            if bytes[p + 1] == b'x' {
+                if let Some(byte) =
+                    field.get(p + 2..p + 4).and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    unescaped.push(byte);
+                    p += 4;
+
+                    continue;
+                }
+            }
+
+            unescaped.push(bytes[p + 1]);
+            p += 2;
+        } else {
+            unescaped.push(bytes[p]);
+            p += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
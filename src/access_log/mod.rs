@@ -0,0 +1,223 @@
+mod extractor;
+mod report;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    io::{self, BufRead},
+};
+
+pub use extractor::{LogFormat, LogLine, UserAgentExtractor};
+pub use report::{Dimension, ParseDimensionError, Report, ReportEntry};
+
+use crate::{models::*, Sections, UserAgentParser};
+
+/// The families and major versions of a parsed user agent.
+type Keys = [(String, Option<String>); 4];
+
+/// The counts of the requests of access logs, aggregated by product, OS, device and engine.
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogStats {
+    requests:           u64,
+    without_user_agent: u64,
+    skipped:            u64,
+    counts:             [HashMap<(String, Option<String>), u64>; 4],
+}
+
+impl AccessLogStats {
+    #[inline]
+    pub fn new() -> AccessLogStats {
+        AccessLogStats::default()
+    }
+
+    /// The number of requests, including the ones without a user agent.
+    #[inline]
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// The number of requests without a user agent.
+    #[inline]
+    pub fn without_user_agent(&self) -> u64 {
+        self.without_user_agent
+    }
+
+    /// The number of lines which are not requests, such as blank lines, directives, or lines which cannot be recognized.
+    #[inline]
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Count a request with a parsed user agent.
+    #[inline]
+    pub fn add(&mut self, parsed_user_agent: &ParsedUserAgent) {
+        self.add_keys(&keys(parsed_user_agent), 1);
+    }
+
+    /// Count a request without a user agent.
+    #[inline]
+    pub fn add_without_user_agent(&mut self) {
+        self.requests += 1;
+        self.without_user_agent += 1;
+    }
+
+    fn add_keys(&mut self, keys: &Keys, count: u64) {
+        self.requests += count;
+
+        for (counts, key) in self.counts.iter_mut().zip(keys.iter()) {
+            match counts.get_mut(key) {
+                Some(c) => *c += count,
+                None => {
+                    counts.insert(key.clone(), count);
+                },
+            }
+        }
+    }
+
+    /// Read an access log and count its requests. Each distinct user agent is parsed only once.
+    ///
+    /// Lines which are not valid UTF-8 are decoded lossily.
+    pub fn read<R: BufRead>(
+        &mut self,
+        user_agent_parser: &UserAgentParser,
+        format: LogFormat,
+        mut reader: R,
+    ) -> Result<(), io::Error> {
+        let mut extractor = UserAgentExtractor::new(format);
+
+        let mut user_agents: HashMap<String, u64> = HashMap::new();
+        let mut buffer = Vec::new();
+
+        loop {
+            buffer.clear();
+
+            if reader.read_until(b'\n', &mut buffer)? == 0 {
+                break;
+            }
+
+            let line = String::from_utf8_lossy(&buffer);
+
+            match extractor.extract(&line) {
+                LogLine::Skipped => self.skipped += 1,
+                LogLine::Request(None) => self.add_without_user_agent(),
+                LogLine::Request(Some(user_agent)) => {
+                    match user_agents.get_mut(user_agent.as_ref()) {
+                        Some(count) => *count += 1,
+                        None => {
+                            user_agents.insert(user_agent.into_owned(), 1);
+                        },
+                    }
+                },
+            }
+        }
+
+        let sections = Sections {
+            cpu: false,
+            ..Sections::ALL
+        };
+
+        for (user_agent, count) in user_agents {
+            let parsed_user_agent = user_agent_parser.parse_sections(&user_agent, sections);
+
+            self.add_keys(&keys(&parsed_user_agent), count);
+        }
+
+        Ok(())
+    }
+
+    /// Create a report of a dimension.
+    ///
+    /// If `by_version` is `true`, the families are further grouped by the major version (except the devices). If `top` is set, the `Other` family does not take one of the top N places. It is merged with the entries beyond the top N into an `Other` bucket at the end.
+    pub fn report(&self, dimension: Dimension, by_version: bool, top: Option<usize>) -> Report {
+        let total = self.requests - self.without_user_agent;
+
+        let mut counts: HashMap<(&str, Option<&str>), u64> = HashMap::new();
+
+        for ((name, major), count) in self.counts[dimension as usize].iter() {
+            let major = if by_version { major.as_deref() } else { None };
+
+            *counts.entry((name.as_str(), major)).or_insert(0) += count;
+        }
+
+        let mut counts: Vec<((&str, Option<&str>), u64)> = counts.into_iter().collect();
+
+        counts.sort_unstable_by(|(a_key, a_count), (b_key, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_key.cmp(b_key))
+        });
+
+        let mut other = None;
+
+        if let Some(top) = top {
+            counts.retain(|&((name, _), count)| {
+                if name == "Other" {
+                    other = Some(other.unwrap_or(0) + count);
+
+                    false
+                } else {
+                    true
+                }
+            });
+
+            if counts.len() > top {
+                let rest: u64 = counts.drain(top..).map(|(_, count)| count).sum();
+
+                other = Some(other.unwrap_or(0) + rest);
+            }
+        }
+
+        let share = |count: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / total as f64
+            }
+        };
+
+        let mut entries: Vec<ReportEntry> = counts
+            .into_iter()
+            .map(|((name, major), count)| ReportEntry {
+                name: name.to_string(),
+                major: major.map(|major| major.to_string()),
+                count,
+                share: share(count),
+            })
+            .collect();
+
+        if let Some(count) = other {
+            entries.push(ReportEntry {
+                name: String::from("Other"),
+                major: None,
+                count,
+                share: share(count),
+            });
+        }
+
+        Report {
+            dimension,
+            total,
+            entries,
+        }
+    }
+}
+
+fn keys(parsed_user_agent: &ParsedUserAgent) -> Keys {
+    #[inline]
+    fn key(name: &Option<Cow<str>>, major: Option<&Cow<str>>) -> (String, Option<String>) {
+        (name.as_deref().unwrap_or("Other").to_string(), major.map(|major| major.to_string()))
+    }
+
+    let ParsedUserAgent {
+        product,
+        os,
+        device,
+        engine,
+        ..
+    } = parsed_user_agent;
+
+    [
+        key(&product.name, product.major.as_ref()),
+        key(&os.name, os.major.as_ref()),
+        key(&device.name, None),
+        key(&engine.name, engine.major.as_ref()),
+    ]
+}
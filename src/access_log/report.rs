@@ -0,0 +1,151 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A dimension by which the requests of an access log are aggregated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    Product,
+    OS,
+    Device,
+    Engine,
+}
+
+/// The error of parsing a `Dimension` from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDimensionError;
+
+impl Display for ParseDimensionError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("The dimension is unknown.")
+    }
+}
+
+impl Error for ParseDimensionError {}
+
+impl Dimension {
+    /// All dimensions in order.
+    pub const ALL: [Dimension; 4] =
+        [Dimension::Product, Dimension::OS, Dimension::Device, Dimension::Engine];
+
+    /// The name of the dimension, such as `product` or `os`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Dimension::Product => "product",
+            Dimension::OS => "os",
+            Dimension::Device => "device",
+            Dimension::Engine => "engine",
+        }
+    }
+
+    #[inline]
+    const fn title(&self) -> &'static str {
+        match self {
+            Dimension::Product => "Product",
+            Dimension::OS => "OS",
+            Dimension::Device => "Device",
+            Dimension::Engine => "Engine",
+        }
+    }
+}
+
+impl Display for Dimension {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Dimension {
+    type Err = ParseDimensionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dimension = match s.to_ascii_lowercase().as_str() {
+            "product" => Dimension::Product,
+            "os" => Dimension::OS,
+            "device" => Dimension::Device,
+            "engine" => Dimension::Engine,
+            _ => return Err(ParseDimensionError),
+        };
+
+        Ok(dimension)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Dimension {
+    /// A dimension is serialized as its name, such as `"product"` or `"os"`.
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Dimension {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(|_| serde::de::Error::custom(format!("unknown dimension `{s}`")))
+    }
+}
+
+/// A row of a `Report`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportEntry {
+    /// The family, such as `Chrome` or `Windows`. The bucket of the entries beyond the top N is named `Other`.
+    pub name:  String,
+    /// The major version. It is `None` if the report is not grouped by version.
+    pub major: Option<String>,
+    pub count: u64,
+    /// The percentage of `count` to the total of the report.
+    pub share: f64,
+}
+
+impl ReportEntry {
+    /// The family with the major version, such as `Chrome 110`.
+    pub fn label(&self) -> String {
+        match self.major.as_deref() {
+            Some(major) => format!("{} {major}", self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// The share of the families (and versions) of a dimension. It is displayed as a table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    pub dimension: Dimension,
+    /// The number of requests with a user agent.
+    pub total:     u64,
+    /// The entries sorted by the count in descending order. The `Other` bucket, if any, is the last one.
+    pub entries:   Vec<ReportEntry>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let labels: Vec<String> = self.entries.iter().map(|entry| entry.label()).collect();
+        let counts: Vec<String> =
+            self.entries.iter().map(|entry| entry.count.to_string()).collect();
+
+        let title = self.dimension.title();
+
+        let label_width =
+            labels.iter().map(|label| label.chars().count()).fold(title.len(), usize::max);
+        let count_width = counts.iter().map(|count| count.len()).fold("Requests".len(), usize::max);
+
+        writeln!(f, "{title:<label_width$}  {:>count_width$}  {:>7}", "Requests", "Share")?;
+
+        for ((label, count), entry) in labels.iter().zip(counts.iter()).zip(self.entries.iter()) {
+            writeln!(f, "{label:<label_width$}  {count:>count_width$}  {:>6.2}%", entry.share)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+    future::{ready, Ready},
+    sync::Arc,
+};
+
+use actix_web::{
+    dev::Payload, error::ErrorInternalServerError, http::header::USER_AGENT, web, Error,
+    FromRequest, HttpMessage, HttpRequest, ResponseError,
+};
+
+use crate::{models::*, MissingUserAgentParser, ParsedSections, Sections, UserAgentParser};
+
+type ErrorHandler = Arc<dyn Fn(MissingUserAgentParser, &HttpRequest) -> Error + Send + Sync>;
+
+/// The configuration of the extractors, registered by `App::app_data`.
+///
+/// By default, a missing `web::Data<UserAgentParser>` results in `500 Internal Server Error`.
+#[derive(Clone, Default)]
+pub struct UserAgentParserConfig {
+    error_handler: Option<ErrorHandler>,
+}
+
+impl UserAgentParserConfig {
+    /// Set a custom error handler which is called when no `web::Data<UserAgentParser>` is in the app data.
+    #[inline]
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(MissingUserAgentParser, &HttpRequest) -> Error + Send + Sync + 'static, {
+        self.error_handler = Some(Arc::new(f));
+
+        self
+    }
+
+    fn from_req(req: &HttpRequest) -> Option<&Self> {
+        req.app_data::<Self>().or_else(|| req.app_data::<web::Data<Self>>().map(|d| d.as_ref()))
+    }
+}
+
+impl Debug for UserAgentParserConfig {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("UserAgentParserConfig")
+            .field("error_handler", &self.error_handler.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+/// Responds `500 Internal Server Error`.
+impl ResponseError for MissingUserAgentParser {}
+
+#[inline]
+fn get_user_agent(req: &HttpRequest) -> Option<&str> {
+    req.headers().get(USER_AGENT).and_then(|value| value.to_str().ok())
+}
+
+/// Parse the user agent once per request. The result is cached in the request extensions. Sections which are needed but were skipped, e.g. by `UserAgentLayer`, are parsed into the cached result.
+fn parsed_user_agent(
+    req: &HttpRequest,
+    sections: Sections,
+) -> Result<ParsedUserAgent<'static>, Error> {
+    let (parsed_sections, missing_sections) = {
+        let extensions = req.extensions();
+
+        match extensions.get::<ParsedUserAgent<'static>>() {
+            Some(parsed_user_agent) => {
+                let parsed_sections =
+                    extensions.get::<ParsedSections>().map_or(Sections::ALL, |s| s.0);
+                let missing_sections = sections.difference(parsed_sections);
+
+                if missing_sections.is_empty() {
+                    return Ok(parsed_user_agent.clone());
+                }
+
+                (parsed_sections, missing_sections)
+            },
+            None => (Sections::NONE, Sections::ALL),
+        }
+    };
+
+    let user_agent_parser = match req.app_data::<web::Data<UserAgentParser>>() {
+        Some(user_agent_parser) => user_agent_parser,
+        None => {
+            let error = match UserAgentParserConfig::from_req(req)
+                .and_then(|config| config.error_handler.as_ref())
+            {
+                Some(error_handler) => error_handler(MissingUserAgentParser, req),
+                None => ErrorInternalServerError(MissingUserAgentParser),
+            };
+
+            return Err(error);
+        },
+    };
+
+    let mut parsed_user_agent = match get_user_agent(req) {
+        Some(user_agent) => {
+            user_agent_parser.parse_sections(user_agent, missing_sections).into_owned()
+        },
+        None => ParsedUserAgent::default(),
+    };
+
+    let mut extensions = req.extensions_mut();
+
+    if let Some(cached) = extensions.get_mut::<ParsedUserAgent<'static>>() {
+        missing_sections.copy(parsed_user_agent, cached);
+
+        parsed_user_agent = cached.clone();
+    } else {
+        extensions.insert(parsed_user_agent.clone());
+    }
+
+    extensions.insert(ParsedSections(parsed_sections.union(missing_sections)));
+
+    Ok(parsed_user_agent)
+}
+
+impl FromRequest for UserAgent<'static> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let user_agent = get_user_agent(req).map(|c| Cow::from(c.to_string()));
+
+        ready(Ok(UserAgent {
+            user_agent,
+        }))
+    }
+}
+
+impl FromRequest for ParsedUserAgent<'static> {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(parsed_user_agent(req, Sections::ALL))
+    }
+}
+
+macro_rules! impl_from_request {
+    ($model:ident, $field:ident) => {
+        impl FromRequest for $model<'static> {
+            type Error = Error;
+            type Future = Ready<Result<Self, Self::Error>>;
+
+            #[inline]
+            fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+                let sections = Sections {
+                    $field: true,
+                    ..Sections::NONE
+                };
+
+                ready(parsed_user_agent(req, sections).map(|parsed| parsed.$field))
+            }
+        }
+    };
+}
+
+impl_from_request!(Product, product);
+impl_from_request!(OS, os);
+impl_from_request!(Device, device);
+impl_from_request!(CPU, cpu);
+impl_from_request!(Engine, engine);
@@ -0,0 +1,145 @@
+use std::{borrow::Cow, convert::Infallible, sync::Arc};
+
+use axum_core::{
+    extract::FromRequestParts,
+    response::{IntoResponse, Response},
+};
+use http::{header::USER_AGENT, request::Parts, StatusCode};
+
+use crate::{models::*, MissingUserAgentParser, ParsedSections, Sections, UserAgentParser};
+
+/// An axum application state which may provide a `UserAgentParser` to the extractors.
+///
+/// If the state does not provide one, the extractors look for an `Extension<Arc<UserAgentParser>>`.
+pub trait UserAgentParserState {
+    fn user_agent_parser(&self) -> Option<&UserAgentParser>;
+}
+
+impl UserAgentParserState for () {
+    #[inline]
+    fn user_agent_parser(&self) -> Option<&UserAgentParser> {
+        None
+    }
+}
+
+impl UserAgentParserState for UserAgentParser {
+    #[inline]
+    fn user_agent_parser(&self) -> Option<&UserAgentParser> {
+        Some(self)
+    }
+}
+
+impl UserAgentParserState for Arc<UserAgentParser> {
+    #[inline]
+    fn user_agent_parser(&self) -> Option<&UserAgentParser> {
+        Some(self.as_ref())
+    }
+}
+
+/// Responds `500 Internal Server Error`.
+impl IntoResponse for MissingUserAgentParser {
+    #[inline]
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+    }
+}
+
+#[inline]
+fn get_user_agent(parts: &Parts) -> Option<&str> {
+    parts.headers.get(USER_AGENT).and_then(|value| value.to_str().ok())
+}
+
+/// Parse the user agent once per request. The result is cached in the request extensions. Sections which are needed but were skipped, e.g. by `UserAgentLayer`, are parsed into the cached result.
+fn parsed_user_agent<'p, S: UserAgentParserState>(
+    parts: &'p mut Parts,
+    state: &S,
+    sections: Sections,
+) -> Result<&'p ParsedUserAgent<'static>, MissingUserAgentParser> {
+    let (parsed_sections, missing_sections) =
+        match parts.extensions.get::<ParsedUserAgent<'static>>() {
+            Some(_) => {
+                let parsed_sections =
+                    parts.extensions.get::<ParsedSections>().map_or(Sections::ALL, |s| s.0);
+
+                (parsed_sections, sections.difference(parsed_sections))
+            },
+            None => (Sections::NONE, Sections::ALL),
+        };
+
+    if !missing_sections.is_empty() {
+        let user_agent_parser = match state.user_agent_parser() {
+            Some(user_agent_parser) => user_agent_parser,
+            None => parts
+                .extensions
+                .get::<Arc<UserAgentParser>>()
+                .ok_or(MissingUserAgentParser)?
+                .as_ref(),
+        };
+
+        let parsed_user_agent = match get_user_agent(parts) {
+            Some(user_agent) => {
+                user_agent_parser.parse_sections(user_agent, missing_sections).into_owned()
+            },
+            None => ParsedUserAgent::default(),
+        };
+
+        match parts.extensions.get_mut::<ParsedUserAgent<'static>>() {
+            Some(cached) => missing_sections.copy(parsed_user_agent, cached),
+            None => {
+                parts.extensions.insert(parsed_user_agent);
+            },
+        }
+
+        parts.extensions.insert(ParsedSections(parsed_sections.union(missing_sections)));
+    }
+
+    Ok(parts.extensions.get::<ParsedUserAgent<'static>>().unwrap())
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for UserAgent<'static> {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = get_user_agent(parts).map(|c| Cow::from(c.to_string()));
+
+        Ok(UserAgent {
+            user_agent,
+        })
+    }
+}
+
+impl<S: UserAgentParserState + Send + Sync> FromRequestParts<S> for ParsedUserAgent<'static> {
+    type Rejection = MissingUserAgentParser;
+
+    #[inline]
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        parsed_user_agent(parts, state, Sections::ALL).cloned()
+    }
+}
+
+macro_rules! impl_from_request_parts {
+    ($model:ident, $field:ident) => {
+        impl<S: UserAgentParserState + Send + Sync> FromRequestParts<S> for $model<'static> {
+            type Rejection = MissingUserAgentParser;
+
+            #[inline]
+            async fn from_request_parts(
+                parts: &mut Parts,
+                state: &S,
+            ) -> Result<Self, Self::Rejection> {
+                let sections = Sections {
+                    $field: true,
+                    ..Sections::NONE
+                };
+
+                parsed_user_agent(parts, state, sections).map(|parsed| parsed.$field.clone())
+            }
+        }
+    };
+}
+
+impl_from_request_parts!(Product, product);
+impl_from_request_parts!(OS, os);
+impl_from_request_parts!(Device, device);
+impl_from_request_parts!(CPU, cpu);
+impl_from_request_parts!(Engine, engine);
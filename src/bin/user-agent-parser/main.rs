@@ -0,0 +1,433 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{builder::PossibleValuesParser, Parser, Subcommand, ValueEnum};
+use serde_json::{json, Map, Value};
+use user_agent_parser::{AccessLogStats, Dimension, LogFormat, ParsedUserAgent, UserAgentParser};
+
+/// The names of the columns in order.
+const COLUMNS: [&str; 18] = [
+    "user_agent",
+    "product.name",
+    "product.major",
+    "product.minor",
+    "product.patch",
+    "os.name",
+    "os.major",
+    "os.minor",
+    "os.patch",
+    "os.patch_minor",
+    "device.name",
+    "device.brand",
+    "device.model",
+    "cpu.architecture",
+    "engine.name",
+    "engine.major",
+    "engine.minor",
+    "engine.patch",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One JSON object per line
+    Jsonl,
+    Csv,
+    Tsv,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum LogFormatArg {
+    /// The nginx/Apache combined log format
+    Combined,
+    /// The IIS W3C extended log format
+    W3c,
+    /// One JSON object per line
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Table,
+    Json,
+}
+
+/// Parse user agents into the product, OS, device, CPU, and engine information.
+#[derive(Debug, Parser)]
+#[command(name = "user-agent-parser", version, about, args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The path to a regexes.yaml file. It can be omitted if the embedded uap-core data is available
+    #[arg(short, long, value_name = "PATH")]
+    regexes: Option<PathBuf>,
+
+    /// The output format
+    #[arg(short, long, value_enum, default_value = "jsonl")]
+    format: Format,
+
+    /// Comma-separated columns to output. All columns are output by default
+    #[arg(
+        short,
+        long,
+        value_name = "COLUMNS",
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(COLUMNS),
+    )]
+    columns: Vec<String>,
+
+    /// User agents to parse. If none is given, user agents are read from stdin, one per line
+    #[arg(value_name = "USER_AGENT")]
+    user_agents: Vec<String>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Read access logs and report the share of the products, OSs, devices, and engines
+    Analyze(AnalyzeArgs),
+    /// Check the rules of a regexes.yaml file for invalid capture references, duplicate regexes, shadowed rules, and nested quantifiers
+    Lint(LintArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct LintArgs {
+    /// A file of user agents, one per line, to find the rules which are shadowed by earlier rules
+    #[arg(short, long, value_name = "FILE")]
+    corpus: Option<PathBuf>,
+
+    /// The path to the regexes.yaml file to check
+    #[arg(value_name = "PATH")]
+    regexes: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+struct AnalyzeArgs {
+    /// The path to a regexes.yaml file. It can be omitted if the embedded uap-core data is available
+    #[arg(short, long, value_name = "PATH")]
+    regexes: Option<PathBuf>,
+
+    /// The format of the access logs
+    #[arg(short, long, value_enum, default_value = "combined")]
+    log_format: LogFormatArg,
+
+    /// The key of the user agent in the JSON objects of the `jsonl` log format
+    #[arg(long, value_name = "KEY", default_value = "http_user_agent")]
+    json_key: String,
+
+    /// Comma-separated dimensions to report. All dimensions are reported by default
+    #[arg(
+        short,
+        long,
+        value_name = "DIMENSIONS",
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(Dimension::ALL.map(|dimension| dimension.as_str())),
+    )]
+    by: Vec<String>,
+
+    /// Group the families by the major version
+    #[arg(short = 'v', long)]
+    by_version: bool,
+
+    /// The number of entries to report for each dimension. The rest are merged into `Other`. `0` means unlimited
+    #[arg(short = 'n', long, value_name = "N", default_value = "10")]
+    top: usize,
+
+    /// The output format of the reports
+    #[arg(short, long, value_enum, default_value = "table")]
+    output: ReportFormat,
+
+    /// Access log files to read. If none is given, the access log is read from stdin
+    #[arg(value_name = "FILE")]
+    files: Vec<PathBuf>,
+}
+
+fn load_user_agent_parser(regexes: Option<&PathBuf>) -> Result<UserAgentParser, Box<dyn Error>> {
+    match regexes {
+        Some(path) => UserAgentParser::from_path(path)
+            .map_err(|error| format!("cannot load {}: {error}", path.display()).into()),
+        #[cfg(feature = "embedded-regexes")]
+        None => Ok(UserAgentParser::default()),
+        #[cfg(not(feature = "embedded-regexes"))]
+        None => Err("the `--regexes` option is required".into()),
+    }
+}
+
+fn column_value<'a>(
+    user_agent: &'a str,
+    parsed: &'a ParsedUserAgent,
+    column: &str,
+) -> Option<&'a str> {
+    match column {
+        "user_agent" => Some(user_agent),
+        "product.name" => parsed.product.name.as_deref(),
+        "product.major" => parsed.product.major.as_deref(),
+        "product.minor" => parsed.product.minor.as_deref(),
+        "product.patch" => parsed.product.patch.as_deref(),
+        "os.name" => parsed.os.name.as_deref(),
+        "os.major" => parsed.os.major.as_deref(),
+        "os.minor" => parsed.os.minor.as_deref(),
+        "os.patch" => parsed.os.patch.as_deref(),
+        "os.patch_minor" => parsed.os.patch_minor.as_deref(),
+        "device.name" => parsed.device.name.as_deref(),
+        "device.brand" => parsed.device.brand.as_deref(),
+        "device.model" => parsed.device.model.as_deref(),
+        "cpu.architecture" => parsed.cpu.architecture.as_deref(),
+        "engine.name" => parsed.engine.name.as_deref(),
+        "engine.major" => parsed.engine.major.as_deref(),
+        "engine.minor" => parsed.engine.minor.as_deref(),
+        "engine.patch" => parsed.engine.patch.as_deref(),
+        _ => None,
+    }
+}
+
+/// Output records in one of the formats.
+enum RecordWriter<W: Write> {
+    Json(W),
+    Delimited(Box<csv::Writer<W>>),
+}
+
+impl<W: Write> RecordWriter<W> {
+    fn new(format: Format, output: W, columns: &[&str]) -> Result<Self, Box<dyn Error>> {
+        let delimiter = match format {
+            Format::Jsonl => return Ok(RecordWriter::Json(output)),
+            Format::Csv => b',',
+            Format::Tsv => b'\t',
+        };
+
+        let mut writer = csv::WriterBuilder::new().delimiter(delimiter).from_writer(output);
+
+        writer.write_record(columns)?;
+
+        Ok(RecordWriter::Delimited(Box::new(writer)))
+    }
+
+    fn write(
+        &mut self,
+        user_agent: &str,
+        parsed: &ParsedUserAgent,
+        columns: &[&str],
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            RecordWriter::Json(output) => {
+                let mut record = Map::new();
+
+                for column in columns {
+                    let value = column_value(user_agent, parsed, column)
+                        .map(Value::from)
+                        .unwrap_or(Value::Null);
+
+                    // `product.name` becomes `{ "product": { "name": ... } }`
+                    match column.split_once('.') {
+                        Some((section, field)) => {
+                            if let Value::Object(section) =
+                                record.entry(section).or_insert_with(|| Value::Object(Map::new()))
+                            {
+                                section.insert(field.to_string(), value);
+                            }
+                        },
+                        None => {
+                            record.insert(column.to_string(), value);
+                        },
+                    }
+                }
+
+                serde_json::to_writer(&mut *output, &record)?;
+                output.write_all(b"\n")?;
+            },
+            RecordWriter::Delimited(writer) => {
+                writer.write_record(
+                    columns
+                        .iter()
+                        .map(|column| column_value(user_agent, parsed, column).unwrap_or_default()),
+                )?;
+            },
+        }
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            RecordWriter::Json(output) => output.flush(),
+            RecordWriter::Delimited(writer) => writer.flush(),
+        }
+    }
+}
+
+fn analyze(args: AnalyzeArgs) -> Result<(), Box<dyn Error>> {
+    let user_agent_parser = load_user_agent_parser(args.regexes.as_ref())?;
+
+    let log_format = match args.log_format {
+        LogFormatArg::Combined => LogFormat::Combined,
+        LogFormatArg::W3c => LogFormat::W3C,
+        LogFormatArg::Jsonl => LogFormat::JsonLines(args.json_key),
+    };
+
+    let mut stats = AccessLogStats::new();
+
+    if args.files.is_empty() {
+        stats.read(&user_agent_parser, log_format, io::stdin().lock())?;
+    } else {
+        for path in args.files.iter() {
+            let file = File::open(path)
+                .map_err(|error| format!("cannot open {}: {error}", path.display()))?;
+
+            stats.read(&user_agent_parser, log_format.clone(), BufReader::new(file))?;
+        }
+    }
+
+    let dimensions: Vec<Dimension> = if args.by.is_empty() {
+        Dimension::ALL.to_vec()
+    } else {
+        args.by.iter().filter_map(|dimension| dimension.parse().ok()).collect()
+    };
+
+    let top = if args.top == 0 { None } else { Some(args.top) };
+
+    let reports =
+        dimensions.into_iter().map(|dimension| stats.report(dimension, args.by_version, top));
+
+    let stdout = io::stdout();
+    let mut output = BufWriter::new(stdout.lock());
+
+    match args.output {
+        ReportFormat::Table => {
+            writeln!(
+                output,
+                "Requests: {} (without a user agent: {})",
+                stats.requests(),
+                stats.without_user_agent()
+            )?;
+
+            for report in reports {
+                writeln!(output)?;
+                write!(output, "{report}")?;
+            }
+        },
+        ReportFormat::Json => {
+            let reports: Vec<_> = reports.collect();
+
+            serde_json::to_writer_pretty(
+                &mut output,
+                &json!({
+                    "requests": stats.requests(),
+                    "without_user_agent": stats.without_user_agent(),
+                    "skipped": stats.skipped(),
+                    "reports": reports,
+                }),
+            )?;
+
+            writeln!(output)?;
+        },
+    }
+
+    output.flush()?;
+
+    Ok(())
+}
+
+/// Returns whether there is no issue.
+fn lint(args: LintArgs) -> Result<bool, Box<dyn Error>> {
+    let user_agent_parser = load_user_agent_parser(Some(&args.regexes))?;
+
+    let corpus = match args.corpus.as_ref() {
+        Some(path) => {
+            let file = File::open(path)
+                .map_err(|error| format!("cannot open {}: {error}", path.display()))?;
+
+            let mut corpus = Vec::new();
+
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                let user_agent = line.trim_end_matches('\r');
+
+                if !user_agent.is_empty() {
+                    corpus.push(user_agent.to_string());
+                }
+            }
+
+            corpus
+        },
+        None => Vec::new(),
+    };
+
+    let issues = user_agent_parser.lint(&corpus);
+
+    let stdout = io::stdout();
+    let mut output = BufWriter::new(stdout.lock());
+
+    for issue in issues.iter() {
+        writeln!(output, "{issue}")?;
+    }
+
+    output.flush()?;
+
+    Ok(issues.is_empty())
+}
+
+fn parse(args: Args) -> Result<(), Box<dyn Error>> {
+    let user_agent_parser = load_user_agent_parser(args.regexes.as_ref())?;
+
+    let columns: Vec<&str> = if args.columns.is_empty() {
+        COLUMNS.to_vec()
+    } else {
+        args.columns.iter().map(|column| column.as_str()).collect()
+    };
+
+    let stdout = io::stdout();
+
+    let mut writer = RecordWriter::new(args.format, BufWriter::new(stdout.lock()), &columns)?;
+
+    let mut write = |user_agent: &str| -> Result<(), Box<dyn Error>> {
+        let parsed = user_agent_parser.parse_all(user_agent);
+
+        writer.write(user_agent, &parsed, &columns)
+    };
+
+    if args.user_agents.is_empty() {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            let user_agent = line.trim_end_matches('\r');
+
+            if !user_agent.is_empty() {
+                write(user_agent)?;
+            }
+        }
+    } else {
+        for user_agent in args.user_agents.iter() {
+            write(user_agent)?;
+        }
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Returns whether the command succeeds without any issue.
+fn run(mut args: Args) -> Result<bool, Box<dyn Error>> {
+    match args.command.take() {
+        Some(Command::Analyze(args)) => analyze(args).map(|()| true),
+        Some(Command::Lint(args)) => lint(args),
+        None => parse(args).map(|()| true),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error}");
+
+            ExitCode::FAILURE
+        },
+    }
+}
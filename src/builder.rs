@@ -0,0 +1,434 @@
+use std::{collections::HashSet, fs, mem, path::PathBuf};
+
+use yaml_rust::{Yaml, YamlLoader};
+
+use crate::{
+    prefilter::Prefilter, regexes::*, RuleError, RuleErrorKind, RuleSection, UserAgentParser,
+    UserAgentParserError,
+};
+
+/// The rules of a source, without the built-in ones.
+#[derive(Debug)]
+pub(crate) struct RuleSet {
+    product_regexes:      Vec<ProductRegex>,
+    os_regexes:           Vec<OSRegex>,
+    device_regexes:       Vec<DeviceRegex>,
+    cpu_regexes:          Vec<CPURegex>,
+    engine_regexes:       Vec<EngineRegex>,
+    device_type_regexes:  Vec<DeviceTypeRegex>,
+    bot_regexes:          Vec<BotRegex>,
+    /// Whether the built-in CPU rules are kept after the CPU rules.
+    cpu_built_in:         bool,
+    /// Whether the built-in engine rules are kept after the engine rules.
+    engine_built_in:      bool,
+    /// Whether the built-in device type rules are kept after the device type rules.
+    device_type_built_in: bool,
+    /// Whether the built-in bot rules are kept after the bot rules.
+    bot_built_in:         bool,
+}
+
+impl RuleSet {
+    #[inline]
+    pub(crate) fn new() -> RuleSet {
+        RuleSet {
+            product_regexes:      Vec::new(),
+            os_regexes:           Vec::new(),
+            device_regexes:       Vec::new(),
+            cpu_regexes:          Vec::new(),
+            engine_regexes:       Vec::new(),
+            device_type_regexes:  Vec::new(),
+            bot_regexes:          Vec::new(),
+            cpu_built_in:         true,
+            engine_built_in:      true,
+            device_type_built_in: true,
+            bot_built_in:         true,
+        }
+    }
+
+    /// Read the rules from YAML data.
+    pub(crate) fn from_str(source: &str) -> Result<RuleSet, UserAgentParserError> {
+        let yamls = YamlLoader::load_from_str(source)?;
+
+        let yaml = match yamls.first().and_then(|yaml| yaml.as_hash()) {
+            Some(yaml) => yaml,
+            None => return Err(UserAgentParserError::IncorrectSource),
+        };
+
+        let get = |section: RuleSection| yaml.get(&Yaml::String(section.as_str().to_string()));
+
+        let mut rules = RuleSet::new();
+
+        if let Some(yaml) = get(RuleSection::Product) {
+            rules.product_regexes =
+                ProductRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::OS) {
+            rules.os_regexes = OSRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::Device) {
+            rules.device_regexes =
+                DeviceRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::DeviceType) {
+            (rules.device_type_regexes, rules.device_type_built_in) =
+                DeviceTypeRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::CPU) {
+            (rules.cpu_regexes, rules.cpu_built_in) =
+                CPURegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::Engine) {
+            (rules.engine_regexes, rules.engine_built_in) =
+                EngineRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        if let Some(yaml) = get(RuleSection::Bot) {
+            (rules.bot_regexes, rules.bot_built_in) =
+                BotRegex::from_yaml(yaml).map_err(|err| err.locate(source))?;
+        }
+
+        Ok(rules)
+    }
+
+    /// Take the rules of a `UserAgentParser` instance, without its built-in rules.
+    pub(crate) fn from_parser(user_agent_parser: UserAgentParser) -> RuleSet {
+        let UserAgentParser {
+            product_regexes,
+            os_regexes,
+            device_regexes,
+            mut cpu_regexes,
+            mut engine_regexes,
+            mut device_type_regexes,
+            mut bot_regexes,
+            built_in_rules: [cpu_built_in, engine_built_in, device_type_built_in, bot_built_in],
+            ..
+        } = user_agent_parser;
+
+        cpu_regexes.truncate(cpu_regexes.len() - cpu_built_in);
+        engine_regexes.truncate(engine_regexes.len() - engine_built_in);
+        device_type_regexes.truncate(device_type_regexes.len() - device_type_built_in);
+        bot_regexes.truncate(bot_regexes.len() - bot_built_in);
+
+        RuleSet {
+            product_regexes,
+            os_regexes,
+            device_regexes,
+            cpu_regexes,
+            engine_regexes,
+            device_type_regexes,
+            bot_regexes,
+            cpu_built_in: cpu_built_in > 0,
+            engine_built_in: engine_built_in > 0,
+            device_type_built_in: device_type_built_in > 0,
+            bot_built_in: bot_built_in > 0,
+        }
+    }
+
+    /// Add the rules after the ones of this rule set. The built-in CPU, engine, device type or bot rules are kept only if both rule sets keep them.
+    pub(crate) fn append(&mut self, mut rules: RuleSet) {
+        self.product_regexes.append(&mut rules.product_regexes);
+        self.os_regexes.append(&mut rules.os_regexes);
+        self.device_regexes.append(&mut rules.device_regexes);
+        self.cpu_regexes.append(&mut rules.cpu_regexes);
+        self.engine_regexes.append(&mut rules.engine_regexes);
+        self.device_type_regexes.append(&mut rules.device_type_regexes);
+        self.bot_regexes.append(&mut rules.bot_regexes);
+        self.cpu_built_in &= rules.cpu_built_in;
+        self.engine_built_in &= rules.engine_built_in;
+        self.device_type_built_in &= rules.device_type_built_in;
+        self.bot_built_in &= rules.bot_built_in;
+    }
+
+    /// Add the rules before the ones of this rule set.
+    #[inline]
+    pub(crate) fn prepend(&mut self, rules: RuleSet) {
+        let rest = mem::replace(self, rules);
+
+        self.append(rest);
+    }
+
+    /// Remove the rules of a section for which the predicate returns `true`. The predicate gets the index of a rule and its regexes.
+    fn remove_rules<F: FnMut(usize, &[Option<&Regex>]) -> bool>(
+        &mut self,
+        section: RuleSection,
+        mut f: F,
+    ) {
+        fn remove<T, F: FnMut(usize, &T) -> bool>(rules: &mut Vec<T>, mut f: F) {
+            let mut index = 0;
+
+            rules.retain(|rule| {
+                let removed = f(index, rule);
+
+                index += 1;
+
+                !removed
+            });
+        }
+
+        match section {
+            RuleSection::Product => {
+                remove(&mut self.product_regexes, |index, r| f(index, &[Some(&r.regex)]))
+            },
+            RuleSection::OS => remove(&mut self.os_regexes, |index, r| f(index, &[Some(&r.regex)])),
+            RuleSection::Device => {
+                remove(&mut self.device_regexes, |index, r| f(index, &[Some(&r.regex)]))
+            },
+            RuleSection::DeviceType => remove(&mut self.device_type_regexes, |index, r| {
+                f(index, &[r.regex.as_ref(), r.device_regex.as_ref(), r.os_regex.as_ref()])
+            }),
+            RuleSection::CPU => {
+                remove(&mut self.cpu_regexes, |index, r| f(index, &[Some(&r.regex)]))
+            },
+            RuleSection::Engine => {
+                remove(&mut self.engine_regexes, |index, r| f(index, &[Some(&r.regex)]))
+            },
+            RuleSection::Bot => {
+                remove(&mut self.bot_regexes, |index, r| f(index, &[Some(&r.regex)]))
+            },
+        }
+    }
+
+    /// Add the built-in rules and build the prefilter.
+    pub(crate) fn into_parser(self) -> UserAgentParser {
+        let RuleSet {
+            product_regexes,
+            os_regexes,
+            device_regexes,
+            mut cpu_regexes,
+            mut engine_regexes,
+            mut device_type_regexes,
+            mut bot_regexes,
+            cpu_built_in,
+            engine_built_in,
+            device_type_built_in,
+            bot_built_in,
+        } = self;
+
+        let mut built_in_rules = [0; 4];
+
+        if cpu_built_in {
+            let built_in = CPURegex::built_in_regexes();
+
+            built_in_rules[0] = built_in.len();
+            cpu_regexes.extend(built_in);
+        }
+
+        if engine_built_in {
+            let built_in = EngineRegex::built_in_regexes();
+
+            built_in_rules[1] = built_in.len();
+            engine_regexes.extend(built_in);
+        }
+
+        if device_type_built_in {
+            let built_in = DeviceTypeRegex::built_in_regexes();
+
+            built_in_rules[2] = built_in.len();
+            device_type_regexes.extend(built_in);
+        }
+
+        if bot_built_in {
+            let built_in = BotRegex::built_in_regexes();
+
+            built_in_rules[3] = built_in.len();
+            bot_regexes.extend(built_in);
+        }
+
+        let prefilter = Prefilter::new([
+            product_regexes.iter().map(|r| (r.regex.as_str(), r.regex.flags())).collect(),
+            os_regexes.iter().map(|r| (r.regex.as_str(), r.regex.flags())).collect(),
+            device_regexes.iter().map(|r| (r.regex.as_str(), r.regex.flags())).collect(),
+            cpu_regexes.iter().map(|r| (r.regex.as_str(), r.regex.flags())).collect(),
+            engine_regexes.iter().map(|r| (r.regex.as_str(), r.regex.flags())).collect(),
+        ]);
+
+        UserAgentParser {
+            product_regexes,
+            os_regexes,
+            device_regexes,
+            cpu_regexes,
+            engine_regexes,
+            device_type_regexes,
+            bot_regexes,
+            built_in_rules,
+            prefilter,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    Path(PathBuf),
+    Str(String),
+    Parser(Box<UserAgentParser>),
+}
+
+#[derive(Debug)]
+struct Entry {
+    source:   Source,
+    prepend:  bool,
+    disabled: Vec<(RuleSection, usize)>,
+}
+
+/// Build a `UserAgentParser` instance by merging several sources of rules in order.
+///
+/// Each source is either appended, so its rules are tried after the rules of the previous sources, or prepended, so they are tried before them. The built-in rules are added once at the end, and the prefilter is rebuilt over the merged rules.
+#[derive(Debug, Default)]
+pub struct UserAgentParserBuilder {
+    entries:          Vec<Entry>,
+    /// The rules disabled before any source has been added. They belong to the next source.
+    pending_disabled: Vec<(RuleSection, usize)>,
+    disabled_regexes: Vec<(RuleSection, String)>,
+}
+
+impl UserAgentParserBuilder {
+    #[inline]
+    pub fn new() -> UserAgentParserBuilder {
+        UserAgentParserBuilder::default()
+    }
+
+    #[inline]
+    fn add(mut self, source: Source, prepend: bool) -> Self {
+        let disabled = mem::take(&mut self.pending_disabled);
+
+        self.entries.push(Entry {
+            source,
+            prepend,
+            disabled,
+        });
+
+        self
+    }
+
+    /// Add the rules of a YAML file after the rules of the previous sources.
+    #[inline]
+    pub fn append_path<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.add(Source::Path(path.into()), false)
+    }
+
+    /// Add the rules of YAML data after the rules of the previous sources.
+    #[inline]
+    pub fn append_str<S: Into<String>>(self, yaml: S) -> Self {
+        self.add(Source::Str(yaml.into()), false)
+    }
+
+    /// Add the rules of a `UserAgentParser` instance (without its built-in rules) after the rules of the previous sources.
+    #[inline]
+    pub fn append_parser(self, user_agent_parser: UserAgentParser) -> Self {
+        self.add(Source::Parser(Box::new(user_agent_parser)), false)
+    }
+
+    /// Add the rules of a YAML file before the rules of the previous sources.
+    #[inline]
+    pub fn prepend_path<P: Into<PathBuf>>(self, path: P) -> Self {
+        self.add(Source::Path(path.into()), true)
+    }
+
+    /// Add the rules of YAML data before the rules of the previous sources.
+    #[inline]
+    pub fn prepend_str<S: Into<String>>(self, yaml: S) -> Self {
+        self.add(Source::Str(yaml.into()), true)
+    }
+
+    /// Add the rules of a `UserAgentParser` instance (without its built-in rules) before the rules of the previous sources.
+    #[inline]
+    pub fn prepend_parser(self, user_agent_parser: UserAgentParser) -> Self {
+        self.add(Source::Parser(Box::new(user_agent_parser)), true)
+    }
+
+    /// Disable a rule of the source added last, by the index of the rule in its section of that source. If no source has been added yet, the rule belongs to the source added next. The `build` method fails if the index is out of range.
+    #[inline]
+    pub fn disable_rule(mut self, section: RuleSection, index: usize) -> Self {
+        match self.entries.last_mut() {
+            Some(entry) => entry.disabled.push((section, index)),
+            None => self.pending_disabled.push((section, index)),
+        }
+
+        self
+    }
+
+    /// Disable the rules of a section in all sources whose regex source is exactly the given one. For device type rules, any of `regex`, `device_regex` and `os_regex` can match. The built-in rules are not affected. The `build` method fails if no rule matches.
+    #[inline]
+    pub fn disable_regex<S: Into<String>>(mut self, section: RuleSection, regex: S) -> Self {
+        self.disabled_regexes.push((section, regex.into()));
+
+        self
+    }
+
+    /// Load the sources and merge their rules.
+    pub fn build(self) -> Result<UserAgentParser, UserAgentParserError> {
+        if let Some(&(section, index)) = self.pending_disabled.first() {
+            return Err(unknown_rule(section, Some(index), None).into());
+        }
+
+        let mut rules = RuleSet::new();
+
+        for entry in self.entries {
+            let mut source_rules = match entry.source {
+                Source::Path(path) => RuleSet::from_str(&fs::read_to_string(path)?)?,
+                Source::Str(yaml) => RuleSet::from_str(&yaml)?,
+                Source::Parser(user_agent_parser) => RuleSet::from_parser(*user_agent_parser),
+            };
+
+            let sections: HashSet<RuleSection> =
+                entry.disabled.iter().map(|(section, _)| *section).collect();
+
+            for section in sections {
+                let mut len = 0;
+
+                source_rules.remove_rules(section, |index, _| {
+                    len = index + 1;
+
+                    entry.disabled.contains(&(section, index))
+                });
+
+                if let Some(&(_, index)) =
+                    entry.disabled.iter().find(|&&(s, index)| s == section && index >= len)
+                {
+                    return Err(unknown_rule(section, Some(index), None).into());
+                }
+            }
+
+            if entry.prepend {
+                rules.prepend(source_rules);
+            } else {
+                rules.append(source_rules);
+            }
+        }
+
+        for (section, source) in self.disabled_regexes {
+            let mut matched = false;
+
+            rules.remove_rules(section, |_, regexes| {
+                let removed = regexes.iter().flatten().any(|regex| regex.as_str() == source);
+
+                matched |= removed;
+
+                removed
+            });
+
+            if !matched {
+                return Err(unknown_rule(section, None, Some(source)).into());
+            }
+        }
+
+        Ok(rules.into_parser())
+    }
+}
+
+#[inline]
+fn unknown_rule(section: RuleSection, index: Option<usize>, regex: Option<String>) -> RuleError {
+    RuleError {
+        section,
+        index,
+        key: None,
+        regex,
+        position: None,
+        kind: RuleErrorKind::UnknownRule,
+    }
+}
@@ -0,0 +1,28 @@
+use crate::UserAgentParser;
+
+/// The `regexes.yaml` file of [uap-core](https://github.com/ua-parser/uap-core) embedded in this crate.
+pub const EMBEDDED_REGEXES: &str = include_str!("../uap-core/regexes.yaml");
+
+const EMBEDDED_PACKAGE_JSON: &str = include_str!("../uap-core/package.json");
+
+/// Get the version of the uap-core data embedded in this crate.
+pub fn embedded_regexes_version() -> &'static str {
+    let json = EMBEDDED_PACKAGE_JSON;
+
+    json.find("\"version\"")
+        .and_then(|index| {
+            let value = json[index + 9..].trim_start().strip_prefix(':')?.trim_start();
+            let value = value.strip_prefix('"')?;
+
+            value.find('"').map(|end| &value[..end])
+        })
+        .unwrap_or_default()
+}
+
+impl Default for UserAgentParser {
+    /// Create a `UserAgentParser` instance with the embedded uap-core data.
+    #[inline]
+    fn default() -> Self {
+        UserAgentParser::from_str(EMBEDDED_REGEXES).expect("the embedded regexes.yaml is incorrect")
+    }
+}
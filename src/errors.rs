@@ -4,15 +4,23 @@ use std::{
     io::Error as IOError,
 };
 
-use onig::Error as RegexError;
+/// The error of compiling a regular expression with the pure-Rust backend.
+#[cfg(feature = "pure-rust")]
+pub type RegexError = Box<fancy_regex::Error>;
+#[cfg(all(feature = "onig", not(feature = "pure-rust")))]
+pub use onig::Error as RegexError;
 use yaml_rust::ScanError;
 
+use crate::RuleSection;
+
 #[derive(Debug)]
 /// Possible errors of `UserAgentParser`.
 pub enum UserAgentParserError {
     ScanError(ScanError),
     IOError(IOError),
-    RegexError(RegexError),
+    /// A section or a rule of YAML data is invalid.
+    InvalidRule(Box<RuleError>),
+    /// YAML data is not a mapping of sections.
     IncorrectSource,
 }
 
@@ -22,7 +30,7 @@ impl Display for UserAgentParserError {
         match self {
             UserAgentParserError::ScanError(err) => Display::fmt(&err, f),
             UserAgentParserError::IOError(err) => Display::fmt(&err, f),
-            UserAgentParserError::RegexError(err) => Display::fmt(&err, f),
+            UserAgentParserError::InvalidRule(err) => Display::fmt(&err, f),
             UserAgentParserError::IncorrectSource => {
                 f.write_str("The source of regular expressions is incorrect.")
             },
@@ -30,7 +38,17 @@ impl Display for UserAgentParserError {
     }
 }
 
-impl Error for UserAgentParserError {}
+impl Error for UserAgentParserError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UserAgentParserError::ScanError(err) => Some(err),
+            UserAgentParserError::IOError(err) => Some(err),
+            UserAgentParserError::InvalidRule(err) => Some(err.as_ref()),
+            UserAgentParserError::IncorrectSource => None,
+        }
+    }
+}
 
 impl From<ScanError> for UserAgentParserError {
     #[inline]
@@ -46,9 +64,120 @@ impl From<IOError> for UserAgentParserError {
     }
 }
 
-impl From<RegexError> for UserAgentParserError {
+impl From<RuleError> for UserAgentParserError {
     #[inline]
-    fn from(error: RegexError) -> UserAgentParserError {
-        UserAgentParserError::RegexError(error)
+    fn from(error: RuleError) -> UserAgentParserError {
+        UserAgentParserError::InvalidRule(Box::new(error))
     }
 }
+
+/// The cause of a `RuleError`.
+#[derive(Debug)]
+pub enum RuleErrorKind {
+    /// The section is not a non-empty sequence of rules.
+    NotASequence,
+    /// The rule is not a mapping.
+    NotAMapping,
+    /// The required key is missing.
+    MissingKey,
+    /// The value of the key is not a string.
+    NotAString,
+    /// The value of the key is not a boolean.
+    NotABoolean,
+    /// The value of the key is not one of the allowed values.
+    InvalidValue(String),
+    /// The regex of the key cannot be compiled.
+    Regex(RegexError),
+    /// The replacement template of the key references a named capture group which the regex does not have.
+    UnknownCaptureName(String),
+    /// The replacement template of the key has a transform which is not supported.
+    UnknownTransform(String),
+    /// The rule to disable by its index or its regex source does not exist.
+    UnknownRule,
+}
+
+/// An invalid section or rule of YAML data, with where it is.
+#[derive(Debug)]
+pub struct RuleError {
+    pub section:  RuleSection,
+    /// The index of the rule in the section. It is `None` if the section itself is invalid.
+    pub index:    Option<usize>,
+    /// The offending key of the rule.
+    pub key:      Option<&'static str>,
+    /// The regex source of the rule, if it has been read.
+    pub regex:    Option<String>,
+    /// The line and the column (both 1-based) of the offending key, or of the rule (or the section) if the key is absent, in YAML data.
+    pub position: Option<(usize, usize)>,
+    pub kind:     RuleErrorKind,
+}
+
+impl Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.write_str(self.section.as_str())?;
+
+        if let Some(index) = self.index {
+            write!(f, "[{index}]")?;
+        }
+
+        if let Some(key) = self.key {
+            write!(f, ".{key}")?;
+        }
+
+        if let Some((line, column)) = self.position {
+            write!(f, " (line {line}, column {column})")?;
+        }
+
+        f.write_str(": ")?;
+
+        match &self.kind {
+            RuleErrorKind::NotASequence => {
+                f.write_str("the section is not a non-empty sequence")?
+            },
+            RuleErrorKind::NotAMapping => f.write_str("the rule is not a mapping")?,
+            RuleErrorKind::MissingKey => f.write_str("the key is missing")?,
+            RuleErrorKind::NotAString => f.write_str("the value is not a string")?,
+            RuleErrorKind::NotABoolean => f.write_str("the value is not a boolean")?,
+            RuleErrorKind::InvalidValue(value) => write!(f, "`{value}` is not a valid value")?,
+            RuleErrorKind::Regex(err) => write!(f, "cannot compile the regex: {err}")?,
+            RuleErrorKind::UnknownCaptureName(name) => {
+                write!(f, "the regex has no capture group named `{name}`")?
+            },
+            RuleErrorKind::UnknownTransform(transform) => {
+                write!(f, "`{transform}` is not a supported transform")?
+            },
+            RuleErrorKind::UnknownRule => f.write_str("there is no such rule to disable")?,
+        }
+
+        if let Some(regex) = self.regex.as_ref() {
+            write!(f, " (regex: `{regex}`)")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for RuleError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            RuleErrorKind::Regex(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The error of the framework integrations when no `UserAgentParser` has been provided to the application.
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingUserAgentParser;
+
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
+impl Display for MissingUserAgentParser {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.write_str("No `UserAgentParser` has been provided to the application.")
+    }
+}
+
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
+impl Error for MissingUserAgentParser {}
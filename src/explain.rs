@@ -0,0 +1,181 @@
+use std::borrow::Cow;
+
+use crate::{
+    get_string,
+    models::*,
+    prefilter::{SECTION_CPU, SECTION_DEVICE, SECTION_ENGINE, SECTION_OS, SECTION_PRODUCT},
+    regexes::{Captures, Field, Regex, Template},
+    UserAgentParser,
+};
+
+/// How a field of a model was built by the matching rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldExplanation<'a> {
+    /// The name of the field, such as `name` or `major`.
+    pub field:       &'static str,
+    /// The raw `*_replacement` template of the rule.
+    pub replacement: Option<&'a str>,
+    /// The capture group used when there is no replacement.
+    pub group:       usize,
+    /// The final value of the field.
+    pub value:       Option<Cow<'a, str>>,
+}
+
+/// A rule which matches the user agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch<'a> {
+    /// The index of the rule in its section, in the order of the YAML data. The built-in CPU and engine rules, if kept, come after the ones of the YAML data.
+    pub index:    usize,
+    /// The source of the regular expression.
+    pub regex:    &'a str,
+    /// The capture groups, including the whole match at index 0. A group which did not participate in the match is `None`.
+    pub captures: Vec<Option<&'a str>>,
+    pub fields:   Vec<FieldExplanation<'a>>,
+}
+
+/// The explanation of how a section of a user agent was parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation<'a, T> {
+    /// The result, which is the same as the one of the corresponding `parse_*` method.
+    pub result:        T,
+    /// The first matching rule, which built the result. It is `None` if no rule matches and the result is the default one.
+    pub matched:       Option<RuleMatch<'a>>,
+    /// The later rules which would have matched too if the first one had not.
+    pub later_matches: Vec<RuleMatch<'a>>,
+}
+
+impl<'a, T> Explanation<'a, T> {
+    fn new<I: Iterator<Item = RuleMatch<'a>>>(result: T, mut matches: I) -> Self {
+        let matched = matches.next();
+        let later_matches = matches.collect();
+
+        Explanation {
+            result,
+            matched,
+            later_matches,
+        }
+    }
+}
+
+fn explain_rule<'a, const N: usize>(
+    index: usize,
+    regex: &'a Regex,
+    fields: [Field<'a>; N],
+    user_agent: &'a str,
+) -> Option<RuleMatch<'a>> {
+    let captures: Captures<'a> = regex.captures(user_agent)?;
+
+    let fields = fields
+        .into_iter()
+        .map(|field| FieldExplanation {
+            field:       field.name,
+            replacement: field.replacement.map(Template::as_str),
+            group:       field.group,
+            value:       get_string(field.group, field.replacement, &captures),
+        })
+        .collect();
+
+    Some(RuleMatch {
+        index,
+        regex: regex.as_str(),
+        captures: (0..captures.len()).map(|i| captures.at(i)).collect(),
+        fields,
+    })
+}
+
+impl UserAgentParser {
+    /// Explain which product rule matches the user agent and how the product information is built.
+    pub fn explain_product<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Explanation<'a, Product<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+        let candidates = candidates.get(SECTION_PRODUCT);
+
+        let matches = candidates.iter().filter_map(|&index| {
+            let product_regex = &self.product_regexes[index];
+
+            explain_rule(index, &product_regex.regex, product_regex.fields(), user_agent)
+        });
+
+        Explanation::new(self.parse_product_candidates(user_agent, candidates), matches)
+    }
+
+    /// Explain which OS rule matches the user agent and how the OS information is built.
+    pub fn explain_os<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Explanation<'a, OS<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+        let candidates = candidates.get(SECTION_OS);
+
+        let matches = candidates.iter().filter_map(|&index| {
+            let os_regex = &self.os_regexes[index];
+
+            explain_rule(index, &os_regex.regex, os_regex.fields(), user_agent)
+        });
+
+        Explanation::new(self.parse_os_candidates(user_agent, candidates), matches)
+    }
+
+    /// Explain which device rule matches the user agent and how the device information is built.
+    pub fn explain_device<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Explanation<'a, Device<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+        let candidates = candidates.get(SECTION_DEVICE);
+
+        let matches = candidates.iter().filter_map(|&index| {
+            let device_regex = &self.device_regexes[index];
+
+            explain_rule(index, &device_regex.regex, device_regex.fields(), user_agent)
+        });
+
+        Explanation::new(self.parse_device_candidates(user_agent, candidates), matches)
+    }
+
+    /// Explain which CPU rule matches the user agent and how the CPU information is built.
+    pub fn explain_cpu<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Explanation<'a, CPU<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+        let candidates = candidates.get(SECTION_CPU);
+
+        let matches = candidates.iter().filter_map(|&index| {
+            let cpu_regex = &self.cpu_regexes[index];
+
+            explain_rule(index, &cpu_regex.regex, cpu_regex.fields(), user_agent)
+        });
+
+        Explanation::new(self.parse_cpu_candidates(user_agent, candidates), matches)
+    }
+
+    /// Explain which engine rule matches the user agent and how the engine information is built.
+    pub fn explain_engine<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Explanation<'a, Engine<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+        let candidates = candidates.get(SECTION_ENGINE);
+
+        let matches = candidates.iter().filter_map(|&index| {
+            let engine_regex = &self.engine_regexes[index];
+
+            explain_rule(index, &engine_regex.regex, engine_regex.fields(), user_agent)
+        });
+
+        Explanation::new(self.parse_engine_candidates(user_agent, candidates), matches)
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use rocket::{
+    fairing::{self, Fairing, Info, Kind},
+    Build, Rocket,
+};
+
+use crate::UserAgentParser;
+
+/// A Rocket fairing which makes sure that a `UserAgentParser` instance is managed before launch.
+///
+/// If no `UserAgentParser` instance has been managed, the fairing loads one from the path in the `user_agent_parser.path` configuration key. Without the key, the embedded uap-core data is used if the `embedded-regexes` feature is enabled. Otherwise, ignition fails.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UserAgentParserFairing;
+
+#[rocket::async_trait]
+impl Fairing for UserAgentParserFairing {
+    #[inline]
+    fn info(&self) -> Info {
+        Info {
+            name: "User Agent Parser", kind: Kind::Ignite
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        if rocket.state::<UserAgentParser>().is_some() {
+            return Ok(rocket);
+        }
+
+        let path = match rocket.figment().extract_inner::<PathBuf>("user_agent_parser.path") {
+            Ok(path) => path,
+            Err(error) => {
+                if error.missing() {
+                    #[cfg(feature = "embedded-regexes")]
+                    return Ok(rocket.manage(UserAgentParser::default()));
+
+                    #[cfg(not(feature = "embedded-regexes"))]
+                    {
+                        rocket::error!(
+                            "The `user_agent_parser.path` configuration is missing. Set it to the \
+                             path of regexes.yaml, or enable the `embedded-regexes` feature to \
+                             use the embedded uap-core data."
+                        );
+
+                        return Err(rocket);
+                    }
+                }
+
+                rocket::error!("The `user_agent_parser.path` configuration is incorrect: {error}");
+
+                return Err(rocket);
+            },
+        };
+
+        match UserAgentParser::from_path(&path) {
+            Ok(user_agent_parser) => Ok(rocket.manage(user_agent_parser)),
+            Err(error) => {
+                rocket::error!("Cannot load {path:?} for the `UserAgentParser`: {error}");
+
+                Err(rocket)
+            },
+        }
+    }
+}
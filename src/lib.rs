@@ -30,6 +30,19 @@ device_parsers:
     model_replacement: '$2'
 ```
 
+Every rule can have a `regex_flag` key with any of `i` (case-insensitive), `m` (`^` and `$` match at line boundaries instead of only at the start and the end of the user agent) and `x` (whitespace and `#` comments are ignored), such as `regex_flag: 'ix'`. An unknown flag is an error.
+
+In the `*_replacement` templates, `$0` is the whole match, `$1` to `$99` are the capture groups, `${name}` is a named capture group such as `(?<name>...)` (`${1}0` separates a group from the following digits), and `$$` is a literal `$`. The templates are compiled when the rules are loaded, and a reference to an unknown name is an error.
+
+A braced reference can be followed by transforms separated by colons, which are applied in order: `upper`, `lower`, `title` (uppercase the first letter of every word and lowercase the others), `dots` (replace underscores with dots), and `strip_suffix=...`.
+
+```yaml
+device_parsers:
+  - regex: '; (\w+) (SM_\w+) Build/'
+    brand_replacement: '${1:title}'
+    model_replacement: '${2:upper:strip_suffix=_TD}'
+```
+
 Then, use the `from_path` (or `from_str` if your YAML data is in-memory) associated function to create a `UserAgentParser` instance.
 
 
@@ -39,6 +52,12 @@ use user_agent_parser::UserAgentParser;
 let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
 ```
 
+If a rule is invalid, the `UserAgentParserError::InvalidRule` error tells which section, rule, and key it is, where it is in the YAML data, and the regex of the rule.
+
+```text
+os_parsers[1].regex (line 6, column 5): the key is missing
+```
+
 Use the `parse_*` methods and input a user-agent string to get information.
 
 ```rust,ignore
@@ -135,6 +154,300 @@ let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
 let product = ua_parser.parse_product("Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 Ubuntu/10.04 (lucid) Firefox/3.6.12").into_owned();
 ```
 
+To get all of the information at once, use the `parse_all` method. It returns a `ParsedUserAgent` instance, which has the `into_owned` method, too.
+
+```rust,ignore
+use user_agent_parser::UserAgentParser;
+
+let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
+
+let parsed = ua_parser.parse_all("Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 Ubuntu/10.04 (lucid) Firefox/3.6.12").into_owned();
+
+println!("{:#?}", parsed.product);
+println!("{:#?}", parsed.os);
+```
+
+The `Product`, `OS`, and `Engine` models have the `version` method to get a comparable `Version` instance. Non-numeric parts such as `b2` or `pre` are kept, and they are lower than the same version without them.
+
+```rust,ignore
+use user_agent_parser::UserAgentParser;
+
+let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
+
+let product = ua_parser.parse_product("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.5481.77 Safari/537.36");
+
+if product.version() >= "110.0" {
+    println!("{}", product.version());
+}
+```
+
+## Device Types
+
+Use the `parse_device_type` method to classify the form factor of the device into a `DeviceType` (`Mobile`, `Tablet`, `Desktop`, `TV`, `Console`, `Wearable`, `Bot`, or `Other`). The classification combines the parsed device and OS information with tokens in the user agent. If the device and OS information have been parsed, use the `classify_device_type` method instead.
+
+```rust,ignore
+use user_agent_parser::{DeviceType, UserAgentParser};
+
+let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
+
+let user_agent = "Mozilla/5.0 (iPad; CPU OS 16_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.0 Mobile/15E148 Safari/604.1";
+
+assert_eq!(DeviceType::Tablet, ua_parser.parse_device_type(user_agent));
+
+let parsed = ua_parser.parse_all(user_agent);
+
+assert_eq!(DeviceType::Tablet, ua_parser.classify_device_type(user_agent, &parsed.device, &parsed.os));
+```
+
+The built-in rules can be overridden by the optional `device_type_parsers` section of **regexes.yaml**. These rules are tried before the built-in ones. Each rule needs a `device_type` and at least one of `regex` (matching the user agent), `device_regex` (matching the device name), and `os_regex` (matching the OS name). All of them have to match.
+
+```yaml
+device_type_parsers:
+  - regex: 'InHouseKiosk'
+    device_type: 'tv'
+  - device_regex: '^Generic Smartphone$'
+    os_regex: '^Android$'
+    device_type: 'mobile'
+```
+
+Write the section as a mapping with `built_in: false` to drop the built-in rules. A user agent that matches none of the `rules` is then classified as `Other`.
+
+```yaml
+device_type_parsers:
+  built_in: false
+  rules:
+    - regex: 'InHouseKiosk'
+      device_type: 'tv'
+```
+
+## CPU and Engine Rules
+
+The CPU architecture and the engine are detected by built-in rules, which can be extended by the optional `cpu_parsers` and `engine_parsers` sections of **regexes.yaml**. These rules are tried before the built-in ones. A CPU rule can have an `architecture_replacement`. An engine rule can have an `engine_replacement` and `engine_v1_replacement` to `engine_v3_replacement`.
+
+```yaml
+cpu_parsers:
+  - regex: '(x86_64)'
+    architecture_replacement: 'amd64'
+
+engine_parsers:
+  - regex: '(Ladybird)/(\d+)\.(\d+)'
+```
+
+To replace the built-in rules instead, write the section as a mapping with `built_in: false`. Leaving out `rules` disables the detection.
+
+```yaml
+engine_parsers:
+  built_in: false
+  rules:
+    - regex: 'ArkWeb/(\d+)\.(\d+)'
+      engine_replacement: 'ArkWeb'
+```
+
+## Bot Detection
+
+Use the `classify_bot` method to detect bots, crawlers, and automated clients. It returns a `Bot` with its `BotCategory` (`SearchCrawler`, `SEOTool`, `Monitoring`, `FeedFetcher`, `AICrawler`, `HTTPLibrary`, `HeadlessBrowser`, or `Other`), its name, and the contact URL in the user agent, or `None` if the user agent does not look automated. The `is_bot` method only tells whether there is a match.
+
+```rust,ignore
+use user_agent_parser::{BotCategory, UserAgentParser};
+
+let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
+
+let bot = ua_parser.classify_bot("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)").unwrap();
+
+assert_eq!(BotCategory::SearchCrawler, bot.category);
+assert_eq!(Some("Googlebot"), bot.name.as_deref());
+assert_eq!(Some("http://www.google.com/bot.html"), bot.url.as_deref());
+
+assert!(ua_parser.is_bot("curl/8.4.0"));
+```
+
+The built-in list can be extended or replaced by the optional `bot_parsers` section of **regexes.yaml**, in the same way as the `cpu_parsers` section. Each rule needs a `bot_category` (`search_crawler`, `seo_tool`, `monitoring`, `feed_fetcher`, `ai_crawler`, `http_library`, `headless_browser`, or `other`), and can have a `name_replacement`. Without it, the first capture group is the name.
+
+```yaml
+bot_parsers:
+  - regex: '(InternalProbe)/(\d+)'
+    bot_category: 'monitoring'
+```
+
+## User-Agent Client Hints
+
+Chromium's UA reduction freezes the OS version, the device model, and the minor product version in the `User-Agent` header, so Windows 11 is parsed as `Windows 10` and Android models become `K`. Collect the `Sec-CH-UA-*` request headers into a `ClientHints` instance and merge them over the parsed results. GREASE brands such as `Not A(Brand` are ignored.
+
+```rust,ignore
+use user_agent_parser::{ClientHints, UserAgentParser};
+
+let ua_parser = UserAgentParser::from_path("/path/to/regexes.yaml").unwrap();
+
+let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.0.0 Safari/537.36";
+
+let client_hints = ClientHints::from_headers([
+    ("Sec-CH-UA", r#""Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110""#),
+    ("Sec-CH-UA-Full-Version-List", r#""Chromium";v="110.0.5481.77", "Not A(Brand";v="24.0.0.0", "Google Chrome";v="110.0.5481.77""#),
+    ("Sec-CH-UA-Platform", r#""Windows""#),
+    ("Sec-CH-UA-Platform-Version", r#""15.0.0""#),
+    ("Sec-CH-UA-Arch", r#""x86""#),
+    ("Sec-CH-UA-Bitness", r#""64""#),
+]);
+
+let parsed = ua_parser.parse_all_with_client_hints(user_agent, &client_hints);
+
+assert_eq!(Some("11"), parsed.os.major.as_deref());
+assert_eq!(Some("5481"), parsed.product.patch.as_deref());
+```
+
+The `merge_product`, `merge_os`, `merge_device`, and `merge_cpu` methods of `ClientHints` can merge the client hints over the models individually.
+
+## Merging Rule Files
+
+Use `UserAgentParser::builder()` to merge several sources of rules in order: YAML files (`append_path`/`prepend_path`), YAML data (`append_str`/`prepend_str`), or `UserAgentParser` instances (`append_parser`/`prepend_parser`). The rules of a prepended source are tried before the rules of the previous sources in every section. Upstream rules can be disabled by their index in their section of the source added last (`disable_rule`), or by their regex source in all sources (`disable_regex`). The `build` method fails with an `InvalidRule` error if a disabled rule does not exist. The built-in rules are added once at the end.
+
+```rust,ignore
+use user_agent_parser::{RuleSection, UserAgentParser};
+
+let ua_parser = UserAgentParser::builder()
+    .append_path("uap-core/regexes.yaml")
+    .disable_rule(RuleSection::Device, 12)
+    .prepend_path("company-regexes.yaml")
+    .build()
+    .unwrap();
+```
+
+## Hot Reloading
+
+`SharedUserAgentParser` is a handle which is cheap to clone. The `load` method gets a snapshot of the current parser without locking, and the `reload_from_path` method builds a new parser from a file and swaps it in atomically. If the file fails to load, the current parser is kept.
+
+```rust,ignore
+use user_agent_parser::{SharedUserAgentParser, UserAgentParser};
+
+let shared = SharedUserAgentParser::new(UserAgentParser::from_path("/path/to/regexes.yaml").unwrap());
+
+let product = shared.load().parse_product("Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/110.0").into_owned();
+
+shared.reload_from_path("/path/to/regexes.yaml").unwrap();
+```
+
+Enable the `watch` feature to reload the parser whenever the file changes. The errors of the reloads are passed to a callback.
+
+```rust,ignore
+let watcher = shared.watch("/path/to/regexes.yaml", |err| eprintln!("{err}")).unwrap();
+
+// the file is watched until `watcher` is dropped
+```
+
+## Explaining Results
+
+When a result is unexpected, the `explain_product`, `explain_os`, `explain_device`, `explain_cpu` and `explain_engine` methods tell which rule matched and how the result was built. An `Explanation` has the same result as the corresponding `parse_*` method, the first matching rule (its index, regex source and capture groups, with the raw `*_replacement` template, the default capture group and the final value of each field), and the later rules which would have matched too.
+
+```rust,ignore
+use user_agent_parser::UserAgentParser;
+
+let ua_parser = UserAgentParser::from_path("uap-core/regexes.yaml").unwrap();
+
+let explanation = ua_parser.explain_device("Mozilla/5.0 (Linux; Android 13; SM-S918B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/110.0.0.0 Mobile Safari/537.36");
+
+if let Some(matched) = explanation.matched {
+    println!("rule #{}: {}", matched.index, matched.regex);
+
+    for field in matched.fields {
+        println!("{}: {:?} -> {:?}", field.field, field.replacement, field.value);
+    }
+}
+
+for later_match in explanation.later_matches {
+    println!("also matched by rule #{}", later_match.index);
+}
+```
+
+## Linting Rules
+
+The loading only checks that YAML data is structurally valid. Use the `lint` method to find rules which are probably wrong. It reports references in the `*_replacement` templates to capture groups which the regex does not have, duplicate regexes, and regexes with nested quantifiers (such as `(a+)+`) which risk catastrophic backtracking. If a corpus of user agents is given, the rules which match some of them but always lose to earlier rules are reported as shadowed.
+
+```rust,ignore
+use user_agent_parser::UserAgentParser;
+
+let ua_parser = UserAgentParser::from_path("custom-regexes.yaml").unwrap();
+
+let corpus = ["Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/110.0"];
+
+for issue in ua_parser.lint(corpus) {
+    println!("{issue}"); // e.g. user_agent_parsers[12]: `v2_replacement` references `$3` but the regex has only 2 capture group(s)
+}
+```
+
+## Embedded uap-core Data
+
+Enable the `embedded-regexes` feature to bundle the **regexes.yaml** file of [uap-core](https://github.com/ua-parser/uap-core) into this crate. Then, a `UserAgentParser` instance can be created by the `default` associated function without any file.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["embedded-regexes"]
+```
+
+```rust,ignore
+use user_agent_parser::UserAgentParser;
+
+let ua_parser = UserAgentParser::default();
+
+println!("{}", user_agent_parser::embedded_regexes_version());
+```
+
+## Pure-Rust Backend
+
+By default, regular expressions are compiled by [Oniguruma](https://github.com/kkos/oniguruma), which is a C library. To build this crate without a C compiler (e.g. for musl or `wasm32` targets), disable the default features and enable the `pure-rust` feature.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+default-features = false
+features = ["pure-rust"]
+```
+
+Rules without lookaround or backreferences are compiled by the [regex](https://crates.io/crates/regex) crate, and the others by the [fancy-regex](https://crates.io/crates/fancy-regex) crate.
+
+The regex backend is chosen by the `onig` feature (enabled by default) or the `pure-rust` feature, and one of them must be enabled. If both are enabled, `pure-rust` is used, but Oniguruma is still compiled and linked. When the default features are disabled for another reason, enable `onig` again.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+default-features = false
+features = ["onig", "serde"]
+```
+
+The `pure-rust` backend supports the syntax of the regex crate plus lookaround, backreferences, atomic groups, and possessive quantifiers. Oniguruma-only syntax such as `\Z`, `\R`, and `\Q…\E` fails to load with it, and the inline `(?m)` is accepted by both backends with different meanings: it makes `.` match newlines in Oniguruma but changes `^` and `$` in the regex crate (use the `m` flag of `regex_flag` instead). Rules which are meant to work with both backends should avoid them.
+
+## Serde Support
+
+Enable the `serde` feature to implement `Serialize` and `Deserialize` for the `Product`, `OS`, `Device`, `CPU`, `Engine`, `ParsedUserAgent`, `ClientHints`, `Brand`, `DeviceType`, `Version`, and (with the `rocket`, `axum`, or `actix-web` feature) `UserAgent` models. Deserialized models own their data, so they can be the `'static` forms.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["serde"]
+```
+
+The field names are the same as the names of the struct fields and they are stable. A field which is `None` is serialized as `null`, and a missing field is deserialized as `None`. A `DeviceType` is serialized as its lowercase name, such as `"mobile"` or `"tv"`, and a `Version` is serialized as a string, such as `"110.0.5481.77"`.
+
+| Model | Fields |
+| --- | --- |
+| `Product` | `name`, `major`, `minor`, `patch` |
+| `OS` | `name`, `major`, `minor`, `patch`, `patch_minor` |
+| `Device` | `name`, `brand`, `model` |
+| `CPU` | `architecture` |
+| `Engine` | `name`, `major`, `minor`, `patch` |
+| `ParsedUserAgent` | `product`, `os`, `device`, `cpu`, `engine` |
+| `UserAgent` | `user_agent` |
+
+```json
+{
+  "product": { "name": "Firefox", "major": "3", "minor": "6", "patch": "12" },
+  "os": { "name": "Ubuntu", "major": "10", "minor": "04", "patch": null, "patch_minor": null },
+  "device": { "name": "Other", "brand": null, "model": null },
+  "cpu": { "architecture": "amd64" },
+  "engine": { "name": "Gecko", "major": "1", "minor": "9", "patch": "2" }
+}
+```
+
 ## Rocket Support
 
 This crate supports the Rocket framework. All you have to do is enabling the `rocket` feature for this crate.
@@ -145,13 +458,13 @@ version = "*"
 features = ["rocket"]
 ```
 
-Let `Rocket` manage a `UserAgentParser` instance, and the `Product`, `OS`, `Device`, `CPU`, `Engine` models of this crate (plus the `UserAgent` model) can be used as *Request Guards*.
+Let `Rocket` manage a `UserAgentParser` instance, and the `Product`, `OS`, `Device`, `CPU`, `Engine` models of this crate (plus the `UserAgent` model) can be used as *Request Guards*. If no `UserAgentParser` instance is managed, the guards fail with the `MissingUserAgentParser` error and the `500 Internal Server Error` status. The `ParsedUserAgent` model (and `&ParsedUserAgent`) can be a request guard, too. The user agent is parsed only once per request, and the result is cached and shared by the guards of all models.
 
 ```rust,ignore
 #[macro_use]
 extern crate rocket;
 
-use user_agent_parser::{UserAgentParser, UserAgent, Product, OS, Device, CPU, Engine};
+use user_agent_parser::{UserAgentParser, UserAgentParserFairing, UserAgent, Product, OS, Device, CPU, Engine};
 
 #[get("/")]
 fn index(user_agent: UserAgent, product: Product, os: OS, device: Device, cpu: CPU, engine: Engine) -> String {
@@ -173,6 +486,197 @@ fn rocket() -> _ {
 }
 ```
 
+To catch a misconfiguration at launch instead, attach the `UserAgentParserFairing`. If no `UserAgentParser` instance has been managed, it loads one from the path in the `user_agent_parser.path` configuration key (or the embedded uap-core data if the key is absent and the `embedded-regexes` feature is enabled), and ignition fails if that is impossible.
+
+```toml
+# Rocket.toml
+[default.user_agent_parser]
+path = "/path/to/regexes.yaml"
+```
+
+```rust,ignore
+#[launch]
+fn rocket() -> _ {
+    rocket::build()
+        .attach(UserAgentParserFairing)
+        .mount("/", routes![index])
+}
+```
+
+## Axum Support
+
+This crate also supports the axum framework. Enable the `axum` feature for this crate.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["axum"]
+```
+
+The owned (`'static`) `Product`, `OS`, `Device`, `CPU`, `Engine`, and `ParsedUserAgent` models of this crate (plus the `UserAgent` model) can be used as *Extractors*. The `UserAgentParser` instance is taken from the state if the state implements the `UserAgentParserState` trait (`Arc<UserAgentParser>` and `UserAgentParser` do), or from an `Extension<Arc<UserAgentParser>>`. If there is none, the `MissingUserAgentParser` rejection is returned. The user agent is parsed only once per request because the result is cached in the request extensions.
+
+```rust,ignore
+use std::sync::Arc;
+
+use axum::{routing::get, Router};
+use user_agent_parser::{UserAgentParser, UserAgent, Product, OS, Device, CPU, Engine};
+
+async fn index(user_agent: UserAgent<'static>, product: Product<'static>, os: OS<'static>, device: Device<'static>, cpu: CPU<'static>, engine: Engine<'static>) -> String {
+    format!("{user_agent:#?}\n{product:#?}\n{os:#?}\n{device:#?}\n{cpu:#?}\n{engine:#?}",
+            user_agent = user_agent,
+            product = product,
+            os = os,
+            device = device,
+            cpu = cpu,
+            engine = engine,
+    )
+}
+
+#[tokio::main]
+async fn main() {
+    let app = Router::new()
+        .route("/", get(index))
+        .with_state(Arc::new(UserAgentParser::from_path("/path/to/regexes.yaml").unwrap()));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await.unwrap();
+
+    axum::serve(listener, app).await.unwrap();
+}
+```
+
+## Actix Web Support
+
+This crate also supports the actix-web framework. Enable the `actix-web` feature for this crate.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["actix-web"]
+```
+
+Register a `web::Data<UserAgentParser>` instance as app data, and the owned (`'static`) `Product`, `OS`, `Device`, `CPU`, `Engine`, and `ParsedUserAgent` models of this crate (plus the `UserAgent` model) can be used as *Extractors*. The user agent is parsed only once per request because the result is cached in the request extensions.
+
+If the parser is missing in the app data, the extractors respond `500 Internal Server Error`. To respond differently, register a `UserAgentParserConfig` instance with a custom error handler.
+
+```rust,ignore
+use actix_web::{error::ErrorServiceUnavailable, get, web, App, HttpServer};
+use user_agent_parser::{UserAgentParser, UserAgentParserConfig, UserAgent, Product, OS, Device, CPU, Engine};
+
+#[get("/")]
+async fn index(user_agent: UserAgent<'static>, product: Product<'static>, os: OS<'static>, device: Device<'static>, cpu: CPU<'static>, engine: Engine<'static>) -> String {
+    format!("{user_agent:#?}\n{product:#?}\n{os:#?}\n{device:#?}\n{cpu:#?}\n{engine:#?}",
+            user_agent = user_agent,
+            product = product,
+            os = os,
+            device = device,
+            cpu = cpu,
+            engine = engine,
+    )
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let user_agent_parser = web::Data::new(UserAgentParser::from_path("/path/to/regexes.yaml").unwrap());
+
+    HttpServer::new(move || {
+        App::new()
+            .app_data(user_agent_parser.clone())
+            .app_data(UserAgentParserConfig::default().error_handler(|error, _| ErrorServiceUnavailable(error)))
+            .service(index)
+    })
+    .bind(("127.0.0.1", 8000))?
+    .run()
+    .await
+}
+```
+
+## Tower Support
+
+Enable the `tower` feature to use the `UserAgentLayer` middleware. It parses the `User-Agent` header of each request once and inserts the owned `ParsedUserAgent<'static>` into the request extensions, so handlers don't need to call the parser. With the `axum` feature, the axum extractors reuse the inserted result.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["tower"]
+```
+
+Options control which sections are parsed (sections which are not parsed are left default) and whether the `Sec-CH-UA-*` Client Hints headers are merged over the results.
+
+```rust,ignore
+use std::sync::Arc;
+
+use tower::ServiceBuilder;
+use user_agent_parser::{ParsedUserAgent, UserAgentLayer, UserAgentParser};
+
+let user_agent_parser = Arc::new(UserAgentParser::from_path("/path/to/regexes.yaml").unwrap());
+
+let service = ServiceBuilder::new()
+    .layer(UserAgentLayer::new(user_agent_parser).device(false).engine(false).client_hints(true))
+    .service_fn(|req: http::Request<String>| async move {
+        let parsed = req.extensions().get::<ParsedUserAgent<'static>>().unwrap();
+
+        Ok::<_, std::convert::Infallible>(http::Response::new(format!("{:#?}", parsed.product)))
+    });
+```
+
+## Access Log Analytics
+
+Enable the `access-log` feature to aggregate the requests of access logs by the product, OS, device and engine families (and major versions). The user agents can be read from the nginx/Apache combined format, the IIS W3C extended format and JSON lines. Each distinct user agent is parsed only once.
+
+```toml
+[dependencies.user-agent-parser]
+version = "*"
+features = ["access-log"]
+```
+
+```rust,ignore
+use std::{fs::File, io::BufReader};
+
+use user_agent_parser::{AccessLogStats, Dimension, LogFormat, UserAgentParser};
+
+let ua_parser = UserAgentParser::from_path("uap-core/regexes.yaml").unwrap();
+
+let mut stats = AccessLogStats::new();
+
+stats.read(&ua_parser, LogFormat::Combined, BufReader::new(File::open("access.log").unwrap())).unwrap();
+
+// the top 10 browsers with their major versions, the rest are merged into `Other`
+let report = stats.report(Dimension::Product, true, Some(10));
+
+println!("{report}");
+```
+
+A `Report` is displayed as a table with the percent share of each entry, and it can be serialized with the `serde` feature.
+
+## Command-line Interface
+
+Enable the `cli` feature to build the `user-agent-parser` binary. It parses the user agents given as arguments, or read from stdin one per line, and prints one record per user agent in JSON Lines (default), CSV or TSV.
+
+```bash
+cargo install user-agent-parser --features cli
+
+user-agent-parser --regexes uap-core/regexes.yaml "Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/110.0"
+
+cat user-agents.txt | user-agent-parser --regexes uap-core/regexes.yaml --format csv --columns user_agent,product.name,product.major,os.name
+```
+
+The available columns are `user_agent`, `product.name`, `product.major`, `product.minor`, `product.patch`, `os.name`, `os.major`, `os.minor`, `os.patch`, `os.patch_minor`, `device.name`, `device.brand`, `device.model`, `cpu.architecture`, `engine.name`, `engine.major`, `engine.minor` and `engine.patch`. All of them are output by default. With the `embedded-regexes` feature, the `--regexes` option can be omitted.
+
+The `analyze` subcommand reads access logs (or stdin) and reports the share of the products, OSs, devices and engines as tables or JSON.
+
+```bash
+user-agent-parser analyze --regexes uap-core/regexes.yaml --log-format combined --by product,os --by-version --top 10 /var/log/nginx/access.log
+
+user-agent-parser analyze --regexes uap-core/regexes.yaml --log-format jsonl --json-key user_agent --output json < access.jsonl
+```
+
+
+The `lint` subcommand checks a rules file, optionally with a corpus of user agents (one per line), and exits with a non-zero code if there is any issue.
+
+```bash
+user-agent-parser lint --corpus user-agents.txt custom-regexes.yaml
+```
+
 ## Testing
 
 ```bash
@@ -189,29 +693,156 @@ cargo test
 ```
 */
 
+#[cfg(feature = "access-log")]
+mod access_log;
+#[cfg(feature = "actix-web")]
+mod actix_extractors;
+#[cfg(feature = "axum")]
+mod axum_extractors;
+mod builder;
+#[cfg(feature = "embedded-regexes")]
+mod embedded;
 mod errors;
+mod explain;
+#[cfg(feature = "rocket")]
+mod fairings;
+mod lint;
 mod models;
+mod prefilter;
 mod regexes;
+mod shared;
+mod structured_headers;
 
 #[cfg(feature = "rocket")]
 mod request_guards;
+#[cfg(feature = "tower")]
+mod tower_layer;
 
 use std::{borrow::Cow, fs, path::Path, str::FromStr};
 
-pub use errors::UserAgentParserError;
+#[cfg(feature = "access-log")]
+pub use access_log::{
+    AccessLogStats, Dimension, LogFormat, LogLine, ParseDimensionError, Report, ReportEntry,
+    UserAgentExtractor,
+};
+#[cfg(feature = "actix-web")]
+pub use actix_extractors::UserAgentParserConfig;
+#[cfg(feature = "axum")]
+pub use axum_extractors::UserAgentParserState;
+use builder::RuleSet;
+pub use builder::UserAgentParserBuilder;
+#[cfg(feature = "embedded-regexes")]
+pub use embedded::{embedded_regexes_version, EMBEDDED_REGEXES};
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
+pub use errors::MissingUserAgentParser;
+pub use errors::{RegexError, RuleError, RuleErrorKind, UserAgentParserError};
+pub use explain::{Explanation, FieldExplanation, RuleMatch};
+#[cfg(feature = "rocket")]
+pub use fairings::UserAgentParserFairing;
+pub use lint::{LintIssue, LintKind};
 pub use models::*;
-use onig::Regex;
+use prefilter::{
+    Prefilter, SECTION_CPU, SECTION_DEVICE, SECTION_ENGINE, SECTION_OS, SECTION_PRODUCT,
+};
 use regexes::*;
-use yaml_rust::{Yaml, YamlLoader};
+pub use shared::SharedUserAgentParser;
+#[cfg(feature = "watch")]
+pub use shared::UserAgentParserWatcher;
+#[cfg(feature = "tower")]
+pub use tower_layer::{UserAgentLayer, UserAgentService};
+
+/// Which sections of a user agent to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Sections {
+    pub(crate) product: bool,
+    pub(crate) os:      bool,
+    pub(crate) device:  bool,
+    pub(crate) cpu:     bool,
+    pub(crate) engine:  bool,
+}
+
+impl Sections {
+    pub(crate) const ALL: Sections =
+        Sections {
+            product: true, os: true, device: true, cpu: true, engine: true
+        };
+    pub(crate) const NONE: Sections =
+        Sections {
+            product: false, os: false, device: false, cpu: false, engine: false
+        };
+}
+
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+impl Sections {
+    /// The sections of this set which are not in the other set.
+    #[inline]
+    pub(crate) fn difference(self, other: Sections) -> Sections {
+        Sections {
+            product: self.product && !other.product,
+            os:      self.os && !other.os,
+            device:  self.device && !other.device,
+            cpu:     self.cpu && !other.cpu,
+            engine:  self.engine && !other.engine,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn union(self, other: Sections) -> Sections {
+        Sections {
+            product: self.product || other.product,
+            os:      self.os || other.os,
+            device:  self.device || other.device,
+            cpu:     self.cpu || other.cpu,
+            engine:  self.engine || other.engine,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(self) -> bool {
+        self == Sections::NONE
+    }
+
+    /// Move the sections of this set from a parsed result to another one.
+    pub(crate) fn copy<'a>(self, from: ParsedUserAgent<'a>, to: &mut ParsedUserAgent<'a>) {
+        if self.product {
+            to.product = from.product;
+        }
+
+        if self.os {
+            to.os = from.os;
+        }
+
+        if self.device {
+            to.device = from.device;
+        }
+
+        if self.cpu {
+            to.cpu = from.cpu;
+        }
+
+        if self.engine {
+            to.engine = from.engine;
+        }
+    }
+}
+
+/// The sections which have been parsed into the `ParsedUserAgent<'static>` in the extensions of a request. Without it, the `ParsedUserAgent<'static>` is considered complete.
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParsedSections(pub(crate) Sections);
 
 #[derive(Debug)]
 pub struct UserAgentParser {
-    replacement_regex: Regex,
-    product_regexes:   Vec<ProductRegex>,
-    os_regexes:        Vec<OSRegex>,
-    device_regexes:    Vec<DeviceRegex>,
-    cpu_regexes:       Vec<CPURegex>,
-    engine_regexes:    Vec<EngineRegex>,
+    product_regexes:     Vec<ProductRegex>,
+    os_regexes:          Vec<OSRegex>,
+    device_regexes:      Vec<DeviceRegex>,
+    cpu_regexes:         Vec<CPURegex>,
+    engine_regexes:      Vec<EngineRegex>,
+    device_type_regexes: Vec<DeviceTypeRegex>,
+    bot_regexes:         Vec<BotRegex>,
+    /// The numbers of the built-in CPU, engine, device type and bot rules at the end of their sections.
+    built_in_rules:      [usize; 4],
+    prefilter:           Prefilter,
 }
 
 impl UserAgentParser {
@@ -225,112 +856,41 @@ impl UserAgentParser {
 
     /// Read the list of regular expressions (YAML data) from a string to create a `UserAgentParser` instance.
     #[allow(clippy::should_implement_trait)]
+    #[inline]
     pub fn from_str<S: AsRef<str>>(yaml: S) -> Result<UserAgentParser, UserAgentParserError> {
-        let yamls = YamlLoader::load_from_str(yaml.as_ref())?;
-
-        if yamls.is_empty() {
-            Err(UserAgentParserError::IncorrectSource)
-        } else {
-            let yaml = &yamls[0];
-
-            match yaml.as_hash() {
-                Some(yaml) => {
-                    let user_agent_parsers =
-                        yaml.get(&Yaml::String("user_agent_parsers".to_string()));
-                    let os_parsers = yaml.get(&Yaml::String("os_parsers".to_string()));
-                    let device_parsers = yaml.get(&Yaml::String("device_parsers".to_string()));
-
-                    let user_agent_regexes = match user_agent_parsers {
-                        Some(user_agent_parsers) => ProductRegex::from_yaml(user_agent_parsers)?,
-                        None => Vec::new(),
-                    };
-
-                    let os_regexes = match os_parsers {
-                        Some(os_parsers) => OSRegex::from_yaml(os_parsers)?,
-                        None => Vec::new(),
-                    };
-
-                    let device_regexes = match device_parsers {
-                        Some(device_parsers) => DeviceRegex::from_yaml(device_parsers)?,
-                        None => Vec::new(),
-                    };
-
-                    Ok(UserAgentParser {
-                        replacement_regex: Regex::new(r"\$(\d){1,9}").unwrap(),
-                        product_regexes: user_agent_regexes,
-                        os_regexes,
-                        device_regexes,
-                        cpu_regexes: CPURegex::built_in_regexes(),
-                        engine_regexes: EngineRegex::built_in_regexes(),
-                    })
-                },
-                None => Err(UserAgentParserError::IncorrectSource),
-            }
-        }
+        RuleSet::from_str(yaml.as_ref()).map(RuleSet::into_parser)
+    }
+
+    /// Create a `UserAgentParserBuilder` instance to merge several sources of rules.
+    #[inline]
+    pub fn builder() -> UserAgentParserBuilder {
+        UserAgentParserBuilder::new()
     }
 }
 
-macro_rules! get_string {
-    ($index:expr, $replacement:expr, $replacement_regex:expr, $captures:expr) => {
-        match $replacement.as_ref() {
-            Some(replacement) => {
-                let replacement_captures_vec: Vec<_> =
-                    $replacement_regex.captures_iter(replacement).collect();
+/// Resolve a field from its replacement, or from the capture group at the index if there is no replacement. The result is trimmed, and an empty result is `None`.
+pub(crate) fn get_string<'a>(
+    index: usize,
+    replacement: Option<&'a Template>,
+    captures: &Captures<'a>,
+) -> Option<Cow<'a, str>> {
+    match replacement {
+        Some(replacement) => {
+            let value = replacement.render(captures);
+
+            if !replacement.has_groups() {
+                return Some(value);
+            }
 
-                if replacement_captures_vec.is_empty() {
-                    Some(Cow::from(replacement))
-                } else {
-                    let mut replacement = replacement.to_string();
-
-                    let captures_len = $captures.len();
-
-                    for replacement_captures in replacement_captures_vec.into_iter().rev() {
-                        let index = replacement_captures.at(1).unwrap().parse::<usize>().unwrap();
-
-                        let pos = replacement_captures.pos(0).unwrap();
-
-                        if index < captures_len {
-                            replacement.replace_range(
-                                pos.0..pos.1,
-                                $captures.at(index).unwrap_or_default(),
-                            );
-                        } else {
-                            replacement.replace_range(pos.0..pos.1, "");
-                        }
-                    }
-
-                    let start_trimmed_replacement = replacement.trim_start();
-
-                    if start_trimmed_replacement.len() != replacement.len() {
-                        replacement = start_trimmed_replacement.trim_end().to_string();
-                    } else {
-                        replacement.truncate(replacement.trim_end().len());
-                    }
-
-                    if replacement.is_empty() {
-                        None
-                    } else {
-                        Some(Cow::from(replacement))
-                    }
-                }
-            },
-            None => match $captures.at($index) {
-                Some(s) => {
-                    let s = s.trim();
-
-                    if s.is_empty() {
-                        None
-                    } else {
-                        Some(Cow::from(s))
-                    }
-                },
-                None => None,
-            },
-        }
-    };
+            let value = value.trim();
 
-    ($index:expr, $captures:expr) => {
-        match $captures.at($index) {
+            if value.is_empty() {
+                None
+            } else {
+                Some(Cow::from(value.to_string()))
+            }
+        },
+        None => match captures.at(index) {
             Some(s) => {
                 let s = s.trim();
 
@@ -341,28 +901,34 @@ macro_rules! get_string {
                 }
             },
             None => None,
-        }
-    };
+        },
+    }
 }
 
 impl UserAgentParser {
     pub fn parse_product<'a, S: AsRef<str> + ?Sized>(&'a self, user_agent: &'a S) -> Product<'a> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        self.parse_product_candidates(user_agent, candidates.get(SECTION_PRODUCT))
+    }
+
+    fn parse_product_candidates<'a>(
+        &'a self,
+        user_agent: &'a str,
+        candidates: &[usize],
+    ) -> Product<'a> {
         let mut product = Product::default();
 
-        for product_regex in self.product_regexes.iter() {
-            if let Some(captures) = product_regex.regex.captures(user_agent.as_ref()) {
-                product.name = get_string!(
-                    1,
-                    product_regex.family_replacement,
-                    self.replacement_regex,
-                    captures
-                );
-                product.major =
-                    get_string!(2, product_regex.v1_replacement, self.replacement_regex, captures);
-                product.minor =
-                    get_string!(3, product_regex.v2_replacement, self.replacement_regex, captures);
-                product.patch =
-                    get_string!(4, product_regex.v3_replacement, self.replacement_regex, captures);
+        for &index in candidates {
+            let product_regex = &self.product_regexes[index];
+
+            if let Some(captures) = product_regex.regex.captures(user_agent) {
+                product.name = get_string(1, product_regex.family_replacement.as_ref(), &captures);
+                product.major = get_string(2, product_regex.v1_replacement.as_ref(), &captures);
+                product.minor = get_string(3, product_regex.v2_replacement.as_ref(), &captures);
+                product.patch = get_string(4, product_regex.v3_replacement.as_ref(), &captures);
 
                 break;
             }
@@ -376,19 +942,25 @@ impl UserAgentParser {
     }
 
     pub fn parse_os<'a, S: AsRef<str> + ?Sized>(&'a self, user_agent: &'a S) -> OS<'a> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        self.parse_os_candidates(user_agent, candidates.get(SECTION_OS))
+    }
+
+    fn parse_os_candidates<'a>(&'a self, user_agent: &'a str, candidates: &[usize]) -> OS<'a> {
         let mut os = OS::default();
 
-        for os_regex in self.os_regexes.iter() {
-            if let Some(captures) = os_regex.regex.captures(user_agent.as_ref()) {
-                os.name = get_string!(1, os_regex.os_replacement, self.replacement_regex, captures);
-                os.major =
-                    get_string!(2, os_regex.os_v1_replacement, self.replacement_regex, captures);
-                os.minor =
-                    get_string!(3, os_regex.os_v2_replacement, self.replacement_regex, captures);
-                os.patch =
-                    get_string!(4, os_regex.os_v3_replacement, self.replacement_regex, captures);
-                os.patch_minor =
-                    get_string!(5, os_regex.os_v4_replacement, self.replacement_regex, captures);
+        for &index in candidates {
+            let os_regex = &self.os_regexes[index];
+
+            if let Some(captures) = os_regex.regex.captures(user_agent) {
+                os.name = get_string(1, os_regex.os_replacement.as_ref(), &captures);
+                os.major = get_string(2, os_regex.os_v1_replacement.as_ref(), &captures);
+                os.minor = get_string(3, os_regex.os_v2_replacement.as_ref(), &captures);
+                os.patch = get_string(4, os_regex.os_v3_replacement.as_ref(), &captures);
+                os.patch_minor = get_string(5, os_regex.os_v4_replacement.as_ref(), &captures);
 
                 break;
             }
@@ -402,28 +974,27 @@ impl UserAgentParser {
     }
 
     pub fn parse_device<'a, S: AsRef<str> + ?Sized>(&'a self, user_agent: &'a S) -> Device<'a> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        self.parse_device_candidates(user_agent, candidates.get(SECTION_DEVICE))
+    }
+
+    fn parse_device_candidates<'a>(
+        &'a self,
+        user_agent: &'a str,
+        candidates: &[usize],
+    ) -> Device<'a> {
         let mut device = Device::default();
 
-        for device_regex in self.device_regexes.iter() {
-            if let Some(captures) = device_regex.regex.captures(user_agent.as_ref()) {
-                device.name = get_string!(
-                    1,
-                    device_regex.device_replacement,
-                    self.replacement_regex,
-                    captures
-                );
-                device.brand = get_string!(
-                    2,
-                    device_regex.brand_replacement,
-                    self.replacement_regex,
-                    captures
-                );
-                device.model = get_string!(
-                    1,
-                    device_regex.model_replacement,
-                    self.replacement_regex,
-                    captures
-                );
+        for &index in candidates {
+            let device_regex = &self.device_regexes[index];
+
+            if let Some(captures) = device_regex.regex.captures(user_agent) {
+                device.name = get_string(1, device_regex.device_replacement.as_ref(), &captures);
+                device.brand = get_string(2, device_regex.brand_replacement.as_ref(), &captures);
+                device.model = get_string(1, device_regex.model_replacement.as_ref(), &captures);
 
                 break;
             }
@@ -437,16 +1008,22 @@ impl UserAgentParser {
     }
 
     pub fn parse_cpu<'a, S: AsRef<str> + ?Sized>(&'a self, user_agent: &'a S) -> CPU<'a> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        self.parse_cpu_candidates(user_agent, candidates.get(SECTION_CPU))
+    }
+
+    fn parse_cpu_candidates<'a>(&'a self, user_agent: &'a str, candidates: &[usize]) -> CPU<'a> {
         let mut cpu = CPU::default();
 
-        for cpu_regex in self.cpu_regexes.iter() {
-            if let Some(captures) = cpu_regex.regex.captures(user_agent.as_ref()) {
-                cpu.architecture = get_string!(
-                    1,
-                    cpu_regex.architecture_replacement,
-                    self.replacement_regex,
-                    captures
-                );
+        for &index in candidates {
+            let cpu_regex = &self.cpu_regexes[index];
+
+            if let Some(captures) = cpu_regex.regex.captures(user_agent) {
+                cpu.architecture =
+                    get_string(1, cpu_regex.architecture_replacement.as_ref(), &captures);
 
                 break;
             }
@@ -456,30 +1033,31 @@ impl UserAgentParser {
     }
 
     pub fn parse_engine<'a, S: AsRef<str> + ?Sized>(&'a self, user_agent: &'a S) -> Engine<'a> {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        self.parse_engine_candidates(user_agent, candidates.get(SECTION_ENGINE))
+    }
+
+    fn parse_engine_candidates<'a>(
+        &'a self,
+        user_agent: &'a str,
+        candidates: &[usize],
+    ) -> Engine<'a> {
         let mut engine = Engine::default();
 
-        for engine_regex in self.engine_regexes.iter() {
-            if let Some(captures) = engine_regex.regex.captures(user_agent.as_ref()) {
-                engine.name =
-                    get_string!(1, engine_regex.name_replacement, self.replacement_regex, captures);
-                engine.major = get_string!(
-                    2,
-                    engine_regex.engine_v1_replacement,
-                    self.replacement_regex,
-                    captures
-                );
-                engine.minor = get_string!(
-                    3,
-                    engine_regex.engine_v2_replacement,
-                    self.replacement_regex,
-                    captures
-                );
-                engine.patch = get_string!(
-                    4,
-                    engine_regex.engine_v3_replacement,
-                    self.replacement_regex,
-                    captures
-                );
+        for &index in candidates {
+            let engine_regex = &self.engine_regexes[index];
+
+            if let Some(captures) = engine_regex.regex.captures(user_agent) {
+                engine.name = get_string(1, engine_regex.engine_replacement.as_ref(), &captures);
+                engine.major =
+                    get_string(2, engine_regex.engine_v1_replacement.as_ref(), &captures);
+                engine.minor =
+                    get_string(3, engine_regex.engine_v2_replacement.as_ref(), &captures);
+                engine.patch =
+                    get_string(4, engine_regex.engine_v3_replacement.as_ref(), &captures);
 
                 break;
             }
@@ -487,6 +1065,129 @@ impl UserAgentParser {
 
         engine
     }
+
+    /// Classify the form factor of the device by the user agent, and its parsed device and OS information.
+    pub fn parse_device_type<S: AsRef<str> + ?Sized>(&self, user_agent: &S) -> DeviceType {
+        let user_agent = user_agent.as_ref();
+
+        let candidates = self.prefilter.scan(user_agent);
+
+        let device = self.parse_device_candidates(user_agent, candidates.get(SECTION_DEVICE));
+        let os = self.parse_os_candidates(user_agent, candidates.get(SECTION_OS));
+
+        self.classify_device_type(user_agent, &device, &os)
+    }
+
+    /// Classify the form factor of the device with the device and OS information which have been parsed from the user agent, e.g. by the `parse_all` method.
+    pub fn classify_device_type<S: AsRef<str> + ?Sized>(
+        &self,
+        user_agent: &S,
+        device: &Device,
+        os: &OS,
+    ) -> DeviceType {
+        let user_agent = user_agent.as_ref();
+        let device_name = device.name.as_deref().unwrap_or_default();
+        let os_name = os.name.as_deref().unwrap_or_default();
+
+        for device_type_regex in self.device_type_regexes.iter() {
+            if device_type_regex.is_match(user_agent, device_name, os_name) {
+                return device_type_regex.device_type;
+            }
+        }
+
+        DeviceType::Other
+    }
+
+    /// Detect a bot, a crawler or an automated client by the user agent, with its category, its name and the contact URL in the user agent. Returns `None` if the user agent does not look automated.
+    pub fn classify_bot<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> Option<Bot<'a>> {
+        let user_agent = user_agent.as_ref();
+
+        for bot_regex in self.bot_regexes.iter() {
+            if let Some(captures) = bot_regex.regex.captures(user_agent) {
+                return Some(Bot {
+                    name:     get_string(1, bot_regex.name_replacement.as_ref(), &captures),
+                    category: bot_regex.category,
+                    url:      contact_url(user_agent).map(Cow::from),
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Whether the user agent is a bot, a crawler or an automated client.
+    #[inline]
+    pub fn is_bot<S: AsRef<str> + ?Sized>(&self, user_agent: &S) -> bool {
+        let user_agent = user_agent.as_ref();
+
+        self.bot_regexes.iter().any(|bot_regex| bot_regex.regex.is_match(user_agent))
+    }
+
+    /// Parse the product, OS, device, CPU, and engine information at once. The user agent is scanned by the prefilter only once for all sections.
+    #[inline]
+    pub fn parse_all<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+    ) -> ParsedUserAgent<'a> {
+        self.parse_sections(user_agent.as_ref(), Sections::ALL)
+    }
+
+    /// Parse the selected sections with one prefilter scan. The other sections are left default.
+    pub(crate) fn parse_sections<'a>(
+        &'a self,
+        user_agent: &'a str,
+        sections: Sections,
+    ) -> ParsedUserAgent<'a> {
+        let mut parsed_user_agent = ParsedUserAgent::default();
+
+        if sections != Sections::NONE {
+            let candidates = self.prefilter.scan(user_agent);
+
+            if sections.product {
+                parsed_user_agent.product =
+                    self.parse_product_candidates(user_agent, candidates.get(SECTION_PRODUCT));
+            }
+
+            if sections.os {
+                parsed_user_agent.os =
+                    self.parse_os_candidates(user_agent, candidates.get(SECTION_OS));
+            }
+
+            if sections.device {
+                parsed_user_agent.device =
+                    self.parse_device_candidates(user_agent, candidates.get(SECTION_DEVICE));
+            }
+
+            if sections.cpu {
+                parsed_user_agent.cpu =
+                    self.parse_cpu_candidates(user_agent, candidates.get(SECTION_CPU));
+            }
+
+            if sections.engine {
+                parsed_user_agent.engine =
+                    self.parse_engine_candidates(user_agent, candidates.get(SECTION_ENGINE));
+            }
+        }
+
+        parsed_user_agent
+    }
+
+    /// Parse the product, OS, device, CPU, and engine information at once, and merge the User-Agent Client Hints over the results.
+    #[inline]
+    pub fn parse_all_with_client_hints<'a, S: AsRef<str> + ?Sized>(
+        &'a self,
+        user_agent: &'a S,
+        client_hints: &ClientHints,
+    ) -> ParsedUserAgent<'a> {
+        let mut parsed_user_agent = self.parse_all(user_agent);
+
+        client_hints.merge(&mut parsed_user_agent);
+
+        parsed_user_agent
+    }
 }
 
 impl FromStr for UserAgentParser {
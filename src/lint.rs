@@ -0,0 +1,349 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    models::*,
+    prefilter::{SECTION_DEVICE, SECTION_OS, SECTION_PRODUCT},
+    regexes::{Field, Regex, RegexFlags},
+    UserAgentParser,
+};
+
+/// The regex and the fields of a rule.
+type Rule<'a> = (&'a Regex, Vec<Field<'a>>);
+
+/// A kind of problem of a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// A replacement references a capture group which the regex does not have. `groups` does not count the whole match.
+    InvalidCaptureReference { key: &'static str, group: usize, groups: usize },
+    /// The regex is the same as the one of an earlier rule, so this rule can never win.
+    DuplicateRegex { earlier: usize },
+    /// Every user agent of the corpus which this rule matches is matched by the earlier rules first.
+    Shadowed { by: Vec<usize>, matches: usize },
+    /// A sub-expression which has a quantifier is repeated, such as `(a+)+`, which risks catastrophic backtracking.
+    NestedQuantifier,
+}
+
+/// A problem of a rule found by the `lint` method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub section: RuleSection,
+    /// The index of the rule in its section.
+    pub index:   usize,
+    pub kind:    LintKind,
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}[{}]: ", self.section, self.index)?;
+
+        match &self.kind {
+            LintKind::InvalidCaptureReference {
+                key,
+                group,
+                groups,
+            } => write!(
+                f,
+                "`{key}` references `${group}` but the regex has only {groups} capture group(s)"
+            ),
+            LintKind::DuplicateRegex {
+                earlier,
+            } => write!(f, "the regex is a duplicate of the rule {earlier}"),
+            LintKind::Shadowed {
+                by,
+                matches,
+            } => {
+                write!(f, "shadowed by the earlier rule(s) ")?;
+
+                for (i, index) in by.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+
+                    write!(f, "{index}")?;
+                }
+
+                write!(f, " on all of its {matches} match(es) in the corpus")
+            },
+            LintKind::NestedQuantifier => {
+                f.write_str("the regex has nested quantifiers which risk catastrophic backtracking")
+            },
+        }
+    }
+}
+
+impl UserAgentParser {
+    /// Check the product, OS, device, CPU, engine, and bot rules for problems which the loading does not catch: out-of-range capture references in the replacements, duplicate regexes, and regexes with nested quantifiers. The built-in CPU, engine, and bot rules are not checked.
+    ///
+    /// If the corpus of user agents is not empty, the rules which match some of them but never win over the earlier rules are reported as shadowed.
+    pub fn lint<I: IntoIterator<Item = S>, S: AsRef<str>>(&self, corpus: I) -> Vec<LintIssue> {
+        let [cpu_built_in, engine_built_in, _, bot_built_in] = self.built_in_rules;
+
+        // the prefilter section of each section, if any, which gives the candidates of the corpus
+        let sections: [(RuleSection, Option<usize>, Vec<Rule>); 6] = [
+            (
+                RuleSection::Product,
+                Some(SECTION_PRODUCT),
+                self.product_regexes.iter().map(|r| (&r.regex, r.fields().to_vec())).collect(),
+            ),
+            (
+                RuleSection::OS,
+                Some(SECTION_OS),
+                self.os_regexes.iter().map(|r| (&r.regex, r.fields().to_vec())).collect(),
+            ),
+            (
+                RuleSection::Device,
+                Some(SECTION_DEVICE),
+                self.device_regexes.iter().map(|r| (&r.regex, r.fields().to_vec())).collect(),
+            ),
+            (
+                RuleSection::CPU,
+                None,
+                self.cpu_regexes[..(self.cpu_regexes.len() - cpu_built_in)]
+                    .iter()
+                    .map(|r| (&r.regex, r.fields().to_vec()))
+                    .collect(),
+            ),
+            (
+                RuleSection::Engine,
+                None,
+                self.engine_regexes[..(self.engine_regexes.len() - engine_built_in)]
+                    .iter()
+                    .map(|r| (&r.regex, r.fields().to_vec()))
+                    .collect(),
+            ),
+            (
+                RuleSection::Bot,
+                None,
+                self.bot_regexes[..(self.bot_regexes.len() - bot_built_in)]
+                    .iter()
+                    .map(|r| (&r.regex, r.fields().to_vec()))
+                    .collect(),
+            ),
+        ];
+
+        let mut issues = Vec::new();
+
+        for (section, _, rules) in sections.iter() {
+            let mut sources: HashMap<(&str, RegexFlags), usize> =
+                HashMap::with_capacity(rules.len());
+
+            for (index, (regex, fields)) in rules.iter().enumerate() {
+                let groups = regex.captures_len() - 1;
+
+                for field in fields {
+                    if let Some(replacement) = field.replacement {
+                        let references: BTreeSet<usize> =
+                            replacement.groups().filter(|&group| group > groups).collect();
+
+                        for group in references {
+                            issues.push(LintIssue {
+                                section: *section,
+                                index,
+                                kind: LintKind::InvalidCaptureReference {
+                                    key: field.key,
+                                    group,
+                                    groups,
+                                },
+                            });
+                        }
+                    }
+                }
+
+                if let Some(&earlier) = sources.get(&(regex.as_str(), regex.flags())) {
+                    issues.push(LintIssue {
+                        section: *section,
+                        index,
+                        kind: LintKind::DuplicateRegex {
+                            earlier,
+                        },
+                    });
+                } else {
+                    sources.insert((regex.as_str(), regex.flags()), index);
+                }
+
+                if has_nested_quantifier(regex.as_str()) {
+                    issues.push(LintIssue {
+                        section: *section,
+                        index,
+                        kind: LintKind::NestedQuantifier,
+                    });
+                }
+            }
+        }
+
+        // (matches, wins, the winning rules of the other matches) of each rule
+        let mut counts: Vec<Vec<(usize, usize, BTreeSet<usize>)>> =
+            sections.iter().map(|(_, _, rules)| vec![Default::default(); rules.len()]).collect();
+
+        // the candidates of the sections without a prefilter are all of their rules
+        let all_indices: Vec<usize> =
+            (0..sections.iter().map(|(_, _, rules)| rules.len()).max().unwrap_or(0)).collect();
+
+        for user_agent in corpus {
+            let user_agent = user_agent.as_ref();
+
+            let candidates = self.prefilter.scan(user_agent);
+
+            for ((_, prefilter_section, rules), counts) in sections.iter().zip(counts.iter_mut()) {
+                let indices = match prefilter_section {
+                    Some(prefilter_section) => candidates.get(*prefilter_section),
+                    None => &all_indices[..rules.len()],
+                };
+
+                let mut winner = None;
+
+                for &index in indices {
+                    if rules[index].0.is_match(user_agent) {
+                        let (matches, wins, winners) = &mut counts[index];
+
+                        *matches += 1;
+
+                        match winner {
+                            Some(winner) => {
+                                winners.insert(winner);
+                            },
+                            None => {
+                                *wins += 1;
+                                winner = Some(index);
+                            },
+                        }
+                    }
+                }
+            }
+        }
+
+        for ((section, ..), counts) in sections.iter().zip(counts) {
+            for (index, (matches, wins, winners)) in counts.into_iter().enumerate() {
+                if matches > 0 && wins == 0 {
+                    issues.push(LintIssue {
+                        section: *section,
+                        index,
+                        kind: LintKind::Shadowed {
+                            by: winners.into_iter().collect(),
+                            matches,
+                        },
+                    });
+                }
+            }
+        }
+
+        // keep the order of the sections, then the order of the rules
+        issues.sort_by_key(|issue| (issue.section as u8, issue.index));
+
+        issues
+    }
+}
+
+/// Whether a regex repeats a group which contains a repetition itself, such as `(a+)+` or `(?:\w+\s?)*`.
+fn has_nested_quantifier(source: &str) -> bool {
+    let bytes = source.as_bytes();
+
+    // whether each open group contains a repetition
+    let mut groups = vec![false];
+
+    let mut p = 0;
+
+    while p < bytes.len() {
+        match bytes[p] {
+            b'\\' => p += 1,
+            b'[' => {
+                p += 1;
+
+                // a `]` right after `[` or `[^` is literal
+                if bytes.get(p) == Some(&b'^') {
+                    p += 1;
+                }
+
+                if bytes.get(p) == Some(&b']') {
+                    p += 1;
+                }
+
+                let mut depth = 1;
+
+                while p < bytes.len() {
+                    match bytes[p] {
+                        b'\\' => p += 1,
+                        b'[' => depth += 1,
+                        b']' => {
+                            depth -= 1;
+
+                            if depth == 0 {
+                                break;
+                            }
+                        },
+                        _ => (),
+                    }
+
+                    p += 1;
+                }
+            },
+            b'(' => groups.push(false),
+            b')' => {
+                let repeated = groups.pop().unwrap_or(false);
+
+                if groups.is_empty() {
+                    groups.push(false);
+                }
+
+                if let Some(quantifier_len) = repetition(&bytes[p + 1..]) {
+                    if repeated {
+                        return true;
+                    }
+
+                    p += quantifier_len;
+
+                    *groups.last_mut().unwrap() = true;
+                } else if repeated {
+                    *groups.last_mut().unwrap() = true;
+                }
+            },
+            _ => {
+                if let Some(quantifier_len) = repetition(&bytes[p..]) {
+                    p += quantifier_len - 1;
+
+                    *groups.last_mut().unwrap() = true;
+                }
+            },
+        }
+
+        p += 1;
+    }
+
+    false
+}
+
+/// The length of the quantifier at the start of the bytes if it can repeat more than once (`*`, `+`, `{n,}`, or `{n,m}` with `m > 1`).
+fn repetition(bytes: &[u8]) -> Option<usize> {
+    match bytes.first()? {
+        b'*' | b'+' => Some(1),
+        b'{' => {
+            let end = bytes.iter().position(|&b| b == b'}')?;
+            let inner = std::str::from_utf8(&bytes[1..end]).ok()?;
+
+            let repeatable = match inner.split_once(',') {
+                Some((min, "")) => min.parse::<usize>().is_ok(),
+                Some((min, max)) => {
+                    let min: usize = if min.is_empty() { 0 } else { min.parse().ok()? };
+                    let max: usize = max.parse().ok()?;
+
+                    max > 1 && max > min
+                },
+                None => {
+                    inner.parse::<usize>().ok()?;
+
+                    false
+                },
+            };
+
+            if repeatable {
+                Some(end + 1)
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// The kind of a bot or an automated client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BotCategory {
+    /// A crawler of a search engine, such as `Googlebot`.
+    SearchCrawler,
+    /// A crawler of an SEO or marketing tool, such as `AhrefsBot`.
+    SEOTool,
+    /// An uptime or performance monitor, such as `UptimeRobot`.
+    Monitoring,
+    /// An RSS or Atom feed reader, such as `Feedly`.
+    FeedFetcher,
+    /// A crawler or a fetcher of an AI or LLM service, such as `GPTBot`.
+    AICrawler,
+    /// An HTTP library or a command-line client, such as `curl`.
+    HTTPLibrary,
+    /// An automated headless browser, such as `HeadlessChrome`.
+    HeadlessBrowser,
+    #[default]
+    Other,
+}
+
+/// The error of parsing a `BotCategory` from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBotCategoryError;
+
+impl Display for ParseBotCategoryError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("The bot category is unknown.")
+    }
+}
+
+impl Error for ParseBotCategoryError {}
+
+impl BotCategory {
+    /// The name used in YAML data, such as `search_crawler` or `ai_crawler`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            BotCategory::SearchCrawler => "search_crawler",
+            BotCategory::SEOTool => "seo_tool",
+            BotCategory::Monitoring => "monitoring",
+            BotCategory::FeedFetcher => "feed_fetcher",
+            BotCategory::AICrawler => "ai_crawler",
+            BotCategory::HTTPLibrary => "http_library",
+            BotCategory::HeadlessBrowser => "headless_browser",
+            BotCategory::Other => "other",
+        }
+    }
+}
+
+impl Display for BotCategory {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BotCategory {
+    type Err = ParseBotCategoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bot_category = match s.to_ascii_lowercase().as_str() {
+            "search_crawler" => BotCategory::SearchCrawler,
+            "seo_tool" => BotCategory::SEOTool,
+            "monitoring" => BotCategory::Monitoring,
+            "feed_fetcher" => BotCategory::FeedFetcher,
+            "ai_crawler" => BotCategory::AICrawler,
+            "http_library" => BotCategory::HTTPLibrary,
+            "headless_browser" => BotCategory::HeadlessBrowser,
+            "other" => BotCategory::Other,
+            _ => return Err(ParseBotCategoryError),
+        };
+
+        Ok(bot_category)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BotCategory {
+    /// A bot category is serialized as its name, such as `"search_crawler"`.
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BotCategory {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <Cow<str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(|_| serde::de::Error::custom(format!("unknown bot category `{s}`")))
+    }
+}
+
+/// A bot detected by the `classify_bot` method.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bot<'a> {
+    pub name:     Option<Cow<'a, str>>,
+    pub category: BotCategory,
+    /// The contact URL in the user agent, such as `http://www.google.com/bot.html`.
+    pub url:      Option<Cow<'a, str>>,
+}
+
+impl<'a> Bot<'a> {
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> Bot<'static> {
+        let name = self.name.map(|c| Cow::from(c.into_owned()));
+        let url = self.url.map(|c| Cow::from(c.into_owned()));
+
+        Bot {
+            name,
+            category: self.category,
+            url,
+        }
+    }
+}
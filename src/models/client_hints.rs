@@ -0,0 +1,387 @@
+use std::borrow::Cow;
+
+use crate::{
+    models::*,
+    structured_headers::{parse_boolean, parse_brand_list, parse_string},
+};
+
+/// Brand names in `Sec-CH-UA` which are named differently by the product rules.
+const BRAND_NAMES: [(&str, &str); 4] = [
+    ("Google Chrome", "Chrome"),
+    ("Microsoft Edge", "Edge"),
+    ("Opera GX", "Opera"),
+    ("Yandex", "Yandex Browser"),
+];
+
+/// Platform names in `Sec-CH-UA-Platform` which are named differently by the OS rules.
+const PLATFORM_NAMES: [(&str, &str); 2] = [("macOS", "Mac OS X"), ("Chromium OS", "Chrome OS")];
+
+/// A brand in the `Sec-CH-UA` or `Sec-CH-UA-Full-Version-List` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Brand {
+    pub name:    String,
+    pub version: Option<String>,
+}
+
+/// The raw values of the User-Agent Client Hints request headers.
+///
+/// Chromium's UA reduction freezes the OS version, the device model and the minor product version in the `User-Agent` header. The client hints carry the real values and can be merged over the results parsed from the `User-Agent` header.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClientHints<'a> {
+    /// `Sec-CH-UA`
+    pub ua:                Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Full-Version-List`
+    pub full_version_list: Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Platform`
+    pub platform:          Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Platform-Version`
+    pub platform_version:  Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Model`
+    pub model:             Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Arch`
+    pub arch:              Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Bitness`
+    pub bitness:           Option<Cow<'a, str>>,
+    /// `Sec-CH-UA-Mobile`
+    pub mobile:            Option<Cow<'a, str>>,
+}
+
+impl<'a> ClientHints<'a> {
+    /// The names of the supported request headers, in lowercase.
+    pub const HEADER_NAMES: [&'static str; 8] = [
+        "sec-ch-ua",
+        "sec-ch-ua-full-version-list",
+        "sec-ch-ua-platform",
+        "sec-ch-ua-platform-version",
+        "sec-ch-ua-model",
+        "sec-ch-ua-arch",
+        "sec-ch-ua-bitness",
+        "sec-ch-ua-mobile",
+    ];
+
+    /// Collect the client hints from `(name, value)` pairs of request headers. Header names are case-insensitive and other headers are ignored.
+    pub fn from_headers<I, K, V>(headers: I) -> ClientHints<'a>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<Cow<'a, str>>, {
+        let mut client_hints = ClientHints::default();
+
+        for (name, value) in headers {
+            client_hints.set_header(name.as_ref(), value);
+        }
+
+        client_hints
+    }
+
+    /// Set the value of a client hint by its header name. Returns `false` if the header is not a supported client hint.
+    pub fn set_header<V: Into<Cow<'a, str>>>(&mut self, name: &str, value: V) -> bool {
+        let field = match name.to_ascii_lowercase().as_str() {
+            "sec-ch-ua" => &mut self.ua,
+            "sec-ch-ua-full-version-list" => &mut self.full_version_list,
+            "sec-ch-ua-platform" => &mut self.platform,
+            "sec-ch-ua-platform-version" => &mut self.platform_version,
+            "sec-ch-ua-model" => &mut self.model,
+            "sec-ch-ua-arch" => &mut self.arch,
+            "sec-ch-ua-bitness" => &mut self.bitness,
+            "sec-ch-ua-mobile" => &mut self.mobile,
+            _ => return false,
+        };
+
+        *field = Some(value.into());
+
+        true
+    }
+
+    /// Whether none of the client hints exists.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ua.is_none()
+            && self.full_version_list.is_none()
+            && self.platform.is_none()
+            && self.platform_version.is_none()
+            && self.model.is_none()
+            && self.arch.is_none()
+            && self.bitness.is_none()
+            && self.mobile.is_none()
+    }
+
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> ClientHints<'static> {
+        let ua = self.ua.map(|c| Cow::from(c.into_owned()));
+        let full_version_list = self.full_version_list.map(|c| Cow::from(c.into_owned()));
+        let platform = self.platform.map(|c| Cow::from(c.into_owned()));
+        let platform_version = self.platform_version.map(|c| Cow::from(c.into_owned()));
+        let model = self.model.map(|c| Cow::from(c.into_owned()));
+        let arch = self.arch.map(|c| Cow::from(c.into_owned()));
+        let bitness = self.bitness.map(|c| Cow::from(c.into_owned()));
+        let mobile = self.mobile.map(|c| Cow::from(c.into_owned()));
+
+        ClientHints {
+            ua,
+            full_version_list,
+            platform,
+            platform_version,
+            model,
+            arch,
+            bitness,
+            mobile,
+        }
+    }
+}
+
+impl<'a> ClientHints<'a> {
+    /// The brands in `Sec-CH-UA` with their significant versions. GREASE brands are ignored.
+    #[inline]
+    pub fn brands(&self) -> Vec<Brand> {
+        parse_brands(self.ua.as_deref())
+    }
+
+    /// The brands in `Sec-CH-UA-Full-Version-List` with their full versions. GREASE brands are ignored.
+    #[inline]
+    pub fn full_version_brands(&self) -> Vec<Brand> {
+        parse_brands(self.full_version_list.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Platform`, if it is a non-empty string.
+    #[inline]
+    pub fn platform_name(&self) -> Option<String> {
+        parse_non_empty_string(self.platform.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Platform-Version`, if it is a non-empty string.
+    #[inline]
+    pub fn platform_version_name(&self) -> Option<String> {
+        parse_non_empty_string(self.platform_version.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Model`, if it is a non-empty string.
+    #[inline]
+    pub fn model_name(&self) -> Option<String> {
+        parse_non_empty_string(self.model.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Arch`, if it is a non-empty string.
+    #[inline]
+    pub fn arch_name(&self) -> Option<String> {
+        parse_non_empty_string(self.arch.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Bitness`, if it is a non-empty string.
+    #[inline]
+    pub fn bitness_name(&self) -> Option<String> {
+        parse_non_empty_string(self.bitness.as_deref())
+    }
+
+    /// The value of `Sec-CH-UA-Mobile`.
+    #[inline]
+    pub fn is_mobile(&self) -> Option<bool> {
+        self.mobile.as_deref().and_then(parse_boolean)
+    }
+}
+
+impl<'a> ClientHints<'a> {
+    /// Merge the product name and version over a `Product` parsed from the `User-Agent` header. A brand other than `Chromium` is preferred, and the full version list is preferred over `Sec-CH-UA`.
+    pub fn merge_product(&self, product: &mut Product) {
+        let mut brands = self.full_version_brands();
+
+        if brands.is_empty() {
+            brands = self.brands();
+        }
+
+        let brand =
+            match brands.iter().find(|brand| brand.name != "Chromium").or_else(|| brands.first()) {
+                Some(brand) => brand,
+                None => return,
+            };
+
+        let name = BRAND_NAMES
+            .iter()
+            .find(|(brand_name, _)| *brand_name == brand.name)
+            .map(|(_, name)| *name)
+            .unwrap_or(brand.name.as_str());
+
+        // keep a more specific name such as `Chrome Mobile`
+        let same_product = product.name.as_deref().is_some_and(|n| n.starts_with(name));
+
+        if !same_product {
+            product.name = Some(Cow::from(name.to_string()));
+        }
+
+        let version = match brand.version.as_deref() {
+            Some(version) => version,
+            None => {
+                if !same_product {
+                    product.major = None;
+                    product.minor = None;
+                    product.patch = None;
+                }
+
+                return;
+            },
+        };
+
+        let mut components = version.split('.').map(|c| c.trim()).filter(|c| !c.is_empty());
+
+        let major = components.next().map(|c| c.to_string());
+        let minor = components.next().map(|c| c.to_string());
+        let patch = components.next().map(|c| c.to_string());
+
+        if minor.is_none() && same_product && product.major.as_deref() == major.as_deref() {
+            // only the significant version is known, and it agrees with the `User-Agent` header
+            return;
+        }
+
+        product.major = major.map(Cow::from);
+        product.minor = minor.map(Cow::from);
+        product.patch = patch.map(Cow::from);
+    }
+
+    /// Merge the platform and its version over an `OS` parsed from the `User-Agent` header. The version of Windows is mapped to `7`, `8`, `8.1`, `10` or `11`.
+    pub fn merge_os(&self, os: &mut OS) {
+        let platform = match self.platform_name() {
+            Some(platform) if platform != "Unknown" => platform,
+            _ => return,
+        };
+
+        let name = PLATFORM_NAMES
+            .iter()
+            .find(|(platform_name, _)| *platform_name == platform)
+            .map(|(_, name)| *name)
+            .unwrap_or(platform.as_str());
+
+        if os.name.as_deref() != Some(name) {
+            os.name = Some(Cow::from(name.to_string()));
+            os.major = None;
+            os.minor = None;
+            os.patch = None;
+            os.patch_minor = None;
+        }
+
+        let version = match self.platform_version_name() {
+            Some(version) => version,
+            None => return,
+        };
+
+        let mut components: Vec<&str> =
+            version.split('.').map(|c| c.trim()).filter(|c| !c.is_empty()).collect();
+
+        if components.is_empty() {
+            return;
+        }
+
+        if name == "Windows" {
+            let major = components[0].parse::<u32>().unwrap_or(0);
+            let minor = components.get(1).and_then(|c| c.parse::<u32>().ok()).unwrap_or(0);
+
+            components = match (major, minor) {
+                (13.., _) => vec!["11"],
+                (1..=12, _) => vec!["10"],
+                (0, 1) => vec!["7"],
+                (0, 2) => vec!["8"],
+                (0, 3) => vec!["8", "1"],
+                _ => return,
+            };
+        } else {
+            // `13.0.0` is reported as `13`, like the `User-Agent` header does
+            while components.len() > 1 && components[components.len() - 1] == "0" {
+                components.pop();
+            }
+        }
+
+        let mut components = components.into_iter().map(|c| Cow::from(c.to_string()));
+
+        os.major = components.next();
+        os.minor = components.next();
+        os.patch = components.next();
+        os.patch_minor = components.next();
+    }
+
+    /// Merge the model over a `Device` parsed from the `User-Agent` header. A generic smartphone is assumed for a mobile device which cannot be recognized.
+    pub fn merge_device(&self, device: &mut Device) {
+        let unknown = device.name.as_deref().map_or(true, |name| name == "Other");
+
+        match self.model_name() {
+            Some(model) => {
+                // the name came from the frozen model, such as `K`
+                let frozen_name = unknown || device.name.is_some() && device.name == device.model;
+
+                if frozen_name || device.name.as_deref() == Some("Generic Smartphone") {
+                    device.name = Some(Cow::from(model.clone()));
+                }
+
+                device.model = Some(Cow::from(model));
+            },
+            None => {
+                if unknown && self.is_mobile() == Some(true) {
+                    device.name = Some(Cow::from("Generic Smartphone"));
+                    device.brand = Some(Cow::from("Generic"));
+                    device.model = Some(Cow::from("Smartphone"));
+                }
+            },
+        }
+    }
+
+    /// Merge the architecture and bitness over a `CPU` parsed from the `User-Agent` header. The architecture is named like the CPU rules do, such as `amd64`, `ia32` and `arm`.
+    pub fn merge_cpu(&self, cpu: &mut CPU) {
+        let arch = match self.arch_name() {
+            Some(arch) => arch.to_ascii_lowercase(),
+            None => return,
+        };
+
+        let bitness = self.bitness_name();
+
+        let architecture = match arch.as_str() {
+            "x86" => {
+                if bitness.as_deref() == Some("64") {
+                    "amd64".to_string()
+                } else {
+                    "ia32".to_string()
+                }
+            },
+            "arm" => "arm".to_string(),
+            _ => arch,
+        };
+
+        cpu.architecture = Some(Cow::from(architecture));
+    }
+
+    /// Merge the client hints over the product, OS, device, and CPU information parsed from the `User-Agent` header.
+    #[inline]
+    pub fn merge(&self, parsed_user_agent: &mut ParsedUserAgent) {
+        self.merge_product(&mut parsed_user_agent.product);
+        self.merge_os(&mut parsed_user_agent.os);
+        self.merge_device(&mut parsed_user_agent.device);
+        self.merge_cpu(&mut parsed_user_agent.cpu);
+    }
+}
+
+/// Whether the brand is a GREASE brand, such as `Not A(Brand` or `Not/A)Brand`.
+#[inline]
+fn is_grease(brand: &str) -> bool {
+    let brand = brand.to_ascii_lowercase();
+
+    brand.contains("not") && brand.contains("brand")
+}
+
+fn parse_brands(value: Option<&str>) -> Vec<Brand> {
+    match value {
+        Some(value) => parse_brand_list(value)
+            .into_iter()
+            .filter(|(name, _)| !name.trim().is_empty() && !is_grease(name))
+            .map(|(name, version)| Brand {
+                name:    name.trim().to_string(),
+                version: version.filter(|version| !version.trim().is_empty()),
+            })
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+#[inline]
+fn parse_non_empty_string(value: Option<&str>) -> Option<String> {
+    value.and_then(parse_string).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(clippy::upper_case_acronyms)]
 pub struct CPU<'a> {
     pub architecture: Option<Cow<'a, str>>,
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Device<'a> {
     pub name:  Option<Cow<'a, str>>,
     pub brand: Option<Cow<'a, str>>,
@@ -0,0 +1,94 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// The form factor of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeviceType {
+    Mobile,
+    Tablet,
+    Desktop,
+    TV,
+    Console,
+    Wearable,
+    Bot,
+    #[default]
+    Other,
+}
+
+/// The error of parsing a `DeviceType` from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDeviceTypeError;
+
+impl Display for ParseDeviceTypeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("The device type is unknown.")
+    }
+}
+
+impl Error for ParseDeviceTypeError {}
+
+impl DeviceType {
+    /// The name used in YAML data, such as `mobile` or `tv`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            DeviceType::Mobile => "mobile",
+            DeviceType::Tablet => "tablet",
+            DeviceType::Desktop => "desktop",
+            DeviceType::TV => "tv",
+            DeviceType::Console => "console",
+            DeviceType::Wearable => "wearable",
+            DeviceType::Bot => "bot",
+            DeviceType::Other => "other",
+        }
+    }
+}
+
+impl Display for DeviceType {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DeviceType {
+    type Err = ParseDeviceTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let device_type = match s.to_ascii_lowercase().as_str() {
+            "mobile" => DeviceType::Mobile,
+            "tablet" => DeviceType::Tablet,
+            "desktop" => DeviceType::Desktop,
+            "tv" => DeviceType::TV,
+            "console" => DeviceType::Console,
+            "wearable" => DeviceType::Wearable,
+            "bot" => DeviceType::Bot,
+            "other" => DeviceType::Other,
+            _ => return Err(ParseDeviceTypeError),
+        };
+
+        Ok(device_type)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DeviceType {
+    /// A device type is serialized as its name, such as `"mobile"` or `"tv"`.
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DeviceType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+
+        s.parse().map_err(|_| serde::de::Error::custom(format!("unknown device type `{s}`")))
+    }
+}
@@ -1,16 +1,28 @@
+mod bot;
+mod client_hints;
 mod cpu;
 mod device;
+mod device_type;
 mod engine;
 mod os;
+mod parsed_user_agent;
 mod product;
+mod rule_section;
+mod version;
 
-#[cfg(feature = "rocket")]
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
 mod user_agent;
 
+pub use bot::{Bot, BotCategory, ParseBotCategoryError};
+pub use client_hints::{Brand, ClientHints};
 pub use cpu::CPU;
 pub use device::Device;
+pub use device_type::{DeviceType, ParseDeviceTypeError};
 pub use engine::Engine;
 pub use os::OS;
+pub use parsed_user_agent::ParsedUserAgent;
 pub use product::Product;
-#[cfg(feature = "rocket")]
+pub use rule_section::{ParseRuleSectionError, RuleSection};
+#[cfg(any(feature = "rocket", feature = "axum", feature = "actix-web"))]
 pub use user_agent::UserAgent;
+pub use version::{ParseVersionError, Version};
@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 
+use super::Version;
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OS<'a> {
     pub name:        Option<Cow<'a, str>>,
     pub major:       Option<Cow<'a, str>>,
@@ -27,4 +30,10 @@ impl<'a> OS<'a> {
             patch_minor,
         }
     }
+
+    /// Get the comparable version made of the `major`, `minor`, `patch`, and `patch_minor` fields.
+    #[inline]
+    pub fn version(&self) -> Version {
+        Version::from_fields(&[&self.major, &self.minor, &self.patch, &self.patch_minor])
+    }
 }
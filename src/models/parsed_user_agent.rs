@@ -0,0 +1,27 @@
+use crate::models::*;
+
+/// The product, OS, device, CPU, and engine information of a user agent, returned by the `parse_all` method.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ParsedUserAgent<'a> {
+    pub product: Product<'a>,
+    pub os:      OS<'a>,
+    pub device:  Device<'a>,
+    pub cpu:     CPU<'a>,
+    pub engine:  Engine<'a>,
+}
+
+impl<'a> ParsedUserAgent<'a> {
+    /// Extracts the owned data.
+    #[inline]
+    pub fn into_owned(self) -> ParsedUserAgent<'static> {
+        ParsedUserAgent {
+            product: self.product.into_owned(),
+            os:      self.os.into_owned(),
+            device:  self.device.into_owned(),
+            cpu:     self.cpu.into_owned(),
+            engine:  self.engine.into_owned(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 
+use super::Version;
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Product<'a> {
     pub name:  Option<Cow<'a, str>>,
     pub major: Option<Cow<'a, str>>,
@@ -24,4 +27,10 @@ impl<'a> Product<'a> {
             patch,
         }
     }
+
+    /// Get the comparable version made of the `major`, `minor`, and `patch` fields.
+    #[inline]
+    pub fn version(&self) -> Version {
+        Version::from_fields(&[&self.major, &self.minor, &self.patch])
+    }
 }
@@ -0,0 +1,72 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// A section of rules in YAML data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleSection {
+    Product,
+    OS,
+    Device,
+    DeviceType,
+    CPU,
+    Engine,
+    Bot,
+}
+
+/// The error of parsing a `RuleSection` from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleSectionError;
+
+impl Display for ParseRuleSectionError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("The rule section is unknown.")
+    }
+}
+
+impl Error for ParseRuleSectionError {}
+
+impl RuleSection {
+    /// The key of the section in YAML data, such as `user_agent_parsers`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            RuleSection::Product => "user_agent_parsers",
+            RuleSection::OS => "os_parsers",
+            RuleSection::Device => "device_parsers",
+            RuleSection::DeviceType => "device_type_parsers",
+            RuleSection::CPU => "cpu_parsers",
+            RuleSection::Engine => "engine_parsers",
+            RuleSection::Bot => "bot_parsers",
+        }
+    }
+}
+
+impl Display for RuleSection {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RuleSection {
+    type Err = ParseRuleSectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rule_section = match s {
+            "user_agent_parsers" => RuleSection::Product,
+            "os_parsers" => RuleSection::OS,
+            "device_parsers" => RuleSection::Device,
+            "device_type_parsers" => RuleSection::DeviceType,
+            "cpu_parsers" => RuleSection::CPU,
+            "engine_parsers" => RuleSection::Engine,
+            "bot_parsers" => RuleSection::Bot,
+            _ => return Err(ParseRuleSectionError),
+        };
+
+        Ok(rule_section)
+    }
+}
@@ -0,0 +1,284 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
+
+/// A comparable version made of dot-separated components, such as `110.0.5481.77` or `4.0b9pre`.
+///
+/// Each component is compared numerically by its leading digits. A component with a non-numeric suffix (e.g. `0b9pre`) is lower than the same number without a suffix, and a component without leading digits (e.g. `beta`) is lower than any number. Missing trailing components are treated as `0`, so `110` equals `110.0`. An empty version (unknown) is lower than any other version.
+#[derive(Debug, Clone, Default)]
+pub struct Version {
+    components: Vec<Component>,
+}
+
+#[derive(Debug, Clone)]
+struct Component {
+    text:   String,
+    number: Option<u64>,
+    digits: usize,
+}
+
+/// The error of parsing a `Version` from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionError;
+
+impl Display for ParseVersionError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("The version is empty or has an empty component.")
+    }
+}
+
+impl Error for ParseVersionError {}
+
+impl Component {
+    fn new(text: &str) -> Option<Component> {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return None;
+        }
+
+        let digits = text.bytes().take_while(|b| b.is_ascii_digit()).count();
+
+        let number = if digits > 0 { text[..digits].parse::<u64>().ok() } else { None };
+
+        Some(Component {
+            text: text.to_string(),
+            number,
+            digits: if number.is_some() { digits } else { 0 },
+        })
+    }
+
+    #[inline]
+    fn suffix(&self) -> &str {
+        &self.text[self.digits..]
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.number == Some(0) && self.suffix().is_empty()
+    }
+
+    fn compare(a: Option<&Component>, b: Option<&Component>) -> Ordering {
+        const ZERO: (Option<u64>, &str) = (Some(0), "");
+
+        let (a_number, a_suffix) = a.map(|c| (c.number, c.suffix())).unwrap_or(ZERO);
+        let (b_number, b_suffix) = b.map(|c| (c.number, c.suffix())).unwrap_or(ZERO);
+
+        // `None` is lower than any number
+        a_number.cmp(&b_number).then_with(|| match (a_suffix.is_empty(), b_suffix.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) if a_number.is_some() => Ordering::Greater,
+            (false, true) if b_number.is_some() => Ordering::Less,
+            _ => a_suffix.cmp(b_suffix),
+        })
+    }
+}
+
+impl Version {
+    /// Create a `Version` instance from components in order. Each component may also contain dots.
+    pub fn from_components<'a, I: IntoIterator<Item = &'a str>>(
+        components: I,
+    ) -> Result<Version, ParseVersionError> {
+        let mut version = Version::default();
+
+        for component in components {
+            for component in component.split('.') {
+                version.components.push(Component::new(component).ok_or(ParseVersionError)?);
+            }
+        }
+
+        Ok(version)
+    }
+
+    /// Create a `Version` instance from the version fields of a model. Fields after the first missing one are ignored, and an incorrect version becomes empty.
+    pub(crate) fn from_fields(fields: &[&Option<Cow<str>>]) -> Version {
+        let components = fields.iter().map_while(|field| field.as_deref());
+
+        Version::from_components(components).unwrap_or_default()
+    }
+
+    /// Whether the version is unknown.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// The number of components.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Get the text of a component.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.components.get(index).map(|c| c.text.as_str())
+    }
+
+    /// Get the leading number of a component.
+    #[inline]
+    pub fn number(&self, index: usize) -> Option<u64> {
+        self.components.get(index).and_then(|c| c.number)
+    }
+
+    /// The leading number of the first component.
+    #[inline]
+    pub fn major(&self) -> Option<u64> {
+        self.number(0)
+    }
+
+    /// The leading number of the second component.
+    #[inline]
+    pub fn minor(&self) -> Option<u64> {
+        self.number(1)
+    }
+
+    /// The leading number of the third component.
+    #[inline]
+    pub fn patch(&self) -> Option<u64> {
+        self.number(2)
+    }
+
+    /// Components without the trailing zeros.
+    #[inline]
+    fn significant_components(&self) -> &[Component] {
+        let len = self.components.iter().rposition(|c| !c.is_zero()).map(|i| i + 1).unwrap_or(0);
+
+        &self.components[..len]
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        for (i, component) in self.components.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+
+            f.write_str(&component.text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Version::from_components([s])
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_empty(), other.is_empty()) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) => (),
+        }
+
+        let len = self.components.len().max(other.components.len());
+
+        for i in 0..len {
+            let ordering = Component::compare(self.components.get(i), other.components.get(i));
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for Version {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let components = self.significant_components();
+
+        // an empty version is not equal to `0`
+        self.is_empty().hash(state);
+
+        for component in components {
+            component.number.hash(state);
+            component.suffix().hash(state);
+        }
+    }
+}
+
+impl PartialEq<str> for Version {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        match other.parse::<Version>() {
+            Ok(other) => self.eq(&other),
+            Err(_) => false,
+        }
+    }
+}
+
+impl PartialEq<&str> for Version {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.eq(*other)
+    }
+}
+
+impl PartialOrd<str> for Version {
+    #[inline]
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        other.parse::<Version>().ok().map(|other| self.cmp(&other))
+    }
+}
+
+impl PartialOrd<&str> for Version {
+    #[inline]
+    fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+        self.partial_cmp(*other)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Version {
+    /// A version is serialized as a string such as `"110.0.5481.77"`. An empty version is `""`.
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+
+        if s.is_empty() {
+            Ok(Version::default())
+        } else {
+            s.parse().map_err(serde::de::Error::custom)
+        }
+    }
+}
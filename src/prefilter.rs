@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+
+use aho_corasick::AhoCorasick;
+use regex_syntax::{
+    hir::{Class, Hir, HirKind},
+    ParserBuilder,
+};
+
+use crate::regexes::RegexFlags;
+
+/// The maximum number of alternative literals kept for one sub-expression.
+const MAX_ALTERNATIVES: usize = 16;
+
+pub(crate) const SECTION_PRODUCT: usize = 0;
+pub(crate) const SECTION_OS: usize = 1;
+pub(crate) const SECTION_DEVICE: usize = 2;
+pub(crate) const SECTION_CPU: usize = 3;
+pub(crate) const SECTION_ENGINE: usize = 4;
+
+const SECTIONS: usize = 5;
+
+/// Indices of rules which may match a user agent, in their original order.
+#[derive(Debug, Default)]
+pub(crate) struct Candidates {
+    sections: [Vec<usize>; SECTIONS],
+}
+
+impl Candidates {
+    #[inline]
+    pub(crate) fn get(&self, section: usize) -> &[usize] {
+        &self.sections[section]
+    }
+}
+
+/// A multi-pattern scanner over the literal substrings required by the product, OS, device, CPU, and engine rules.
+///
+/// A rule becomes a candidate when one of its required literals is found in the user agent. Rules whose required literals cannot be determined (for example, those using lookaround or backreferences) are always candidates, so the first-match-wins results are the same as trying every rule.
+#[derive(Debug)]
+pub(crate) struct Prefilter {
+    automaton: Option<AhoCorasick>,
+    targets:   Vec<Vec<(usize, usize)>>,
+    always:    [Vec<usize>; SECTIONS],
+}
+
+impl Prefilter {
+    /// Create a `Prefilter` instance. Each section is a list of `(regex source, flags)` in the rule order.
+    pub(crate) fn new(sections: [Vec<(&str, RegexFlags)>; SECTIONS]) -> Prefilter {
+        let mut patterns: Vec<String> = Vec::new();
+        let mut pattern_indices: HashMap<String, usize> = HashMap::new();
+        let mut targets: Vec<Vec<(usize, usize)>> = Vec::new();
+        let mut always: [Vec<usize>; SECTIONS] = Default::default();
+
+        for (section, rules) in sections.iter().enumerate() {
+            for (index, (source, flags)) in rules.iter().enumerate() {
+                match required_literals(source, *flags) {
+                    Some(literals) => {
+                        for literal in literals {
+                            let key = literal.to_ascii_lowercase();
+
+                            let pattern_index = *pattern_indices.entry(key).or_insert_with(|| {
+                                patterns.push(literal);
+                                targets.push(Vec::new());
+
+                                patterns.len() - 1
+                            });
+
+                            targets[pattern_index].push((section, index));
+                        }
+                    },
+                    None => always[section].push(index),
+                }
+            }
+        }
+
+        if patterns.is_empty() {
+            return Prefilter {
+                automaton: None,
+                targets,
+                always,
+            };
+        }
+
+        match AhoCorasick::builder().ascii_case_insensitive(true).build(&patterns) {
+            Ok(automaton) => Prefilter {
+                automaton: Some(automaton),
+                targets,
+                always,
+            },
+            Err(_) => {
+                // fall back to trying every rule
+                for (section, rules) in sections.iter().enumerate() {
+                    always[section] = (0..rules.len()).collect();
+                }
+
+                Prefilter {
+                    automaton: None,
+                    targets: Vec::new(),
+                    always,
+                }
+            },
+        }
+    }
+
+    /// Find the candidate rules of every section with one scan over the user agent.
+    pub(crate) fn scan(&self, user_agent: &str) -> Candidates {
+        let mut sections = self.always.clone();
+
+        if let Some(automaton) = self.automaton.as_ref() {
+            let mut matched = vec![false; self.targets.len()];
+
+            for m in automaton.find_overlapping_iter(user_agent) {
+                let pattern_index = m.pattern().as_usize();
+
+                if matched[pattern_index] {
+                    continue;
+                }
+
+                matched[pattern_index] = true;
+
+                for &(section, index) in self.targets[pattern_index].iter() {
+                    sections[section].push(index);
+                }
+            }
+
+            for indices in sections.iter_mut() {
+                indices.sort_unstable();
+                indices.dedup();
+            }
+        }
+
+        Candidates {
+            sections,
+        }
+    }
+}
+
+/// Get a set of literals of which at least one must appear in any text matched by the regular expression. Returns `None` if the set cannot be determined.
+fn required_literals(source: &str, flags: RegexFlags) -> Option<Vec<String>> {
+    let hir = ParserBuilder::new()
+        .case_insensitive(flags.ignore_case)
+        .multi_line(flags.multi_line)
+        .ignore_whitespace(flags.extended)
+        .build()
+        .parse(source)
+        .ok()?;
+
+    let literals = required(&hir)?;
+
+    if literals.iter().any(|literal| literal.is_empty()) {
+        None
+    } else {
+        Some(literals)
+    }
+}
+
+/// A set of alternative literals of which one must be a substring of any match.
+fn required(hir: &Hir) -> Option<Vec<String>> {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => None,
+        HirKind::Literal(_) | HirKind::Class(_) => exact(hir),
+        HirKind::Capture(capture) => required(&capture.sub),
+        HirKind::Repetition(repetition) => {
+            if repetition.min > 0 {
+                required(&repetition.sub)
+            } else {
+                None
+            }
+        },
+        HirKind::Concat(subs) => {
+            let mut best: Option<Vec<String>> = None;
+
+            let mut consider = |literals: Vec<String>| {
+                if literals.iter().any(|literal| literal.is_empty()) {
+                    return;
+                }
+
+                let better = match best.as_ref() {
+                    Some(best) => score(&literals) > score(best),
+                    None => true,
+                };
+
+                if better {
+                    best = Some(literals);
+                }
+            };
+
+            // runs of adjacent sub-expressions with a small set of exact matches are joined into longer literals
+            let mut run: Option<Vec<String>> = None;
+
+            for sub in subs {
+                match exact(sub) {
+                    Some(literals) => {
+                        run = match run.take() {
+                            Some(prefixes) => match cross(&prefixes, &literals) {
+                                Some(joined) => Some(joined),
+                                None => {
+                                    consider(prefixes);
+
+                                    Some(literals)
+                                },
+                            },
+                            None => Some(literals),
+                        };
+                    },
+                    None => {
+                        if let Some(prefixes) = run.take() {
+                            consider(prefixes);
+                        }
+
+                        if let Some(literals) = required(sub) {
+                            consider(literals);
+                        }
+                    },
+                }
+            }
+
+            if let Some(prefixes) = run {
+                consider(prefixes);
+            }
+
+            best
+        },
+        HirKind::Alternation(subs) => {
+            let mut literals = Vec::new();
+
+            for sub in subs {
+                literals.extend(required(sub)?);
+            }
+
+            literals.sort_unstable();
+            literals.dedup();
+
+            if literals.len() > MAX_ALTERNATIVES {
+                None
+            } else {
+                Some(literals)
+            }
+        },
+    }
+}
+
+/// The complete set of texts matched by the expression, if it is small.
+fn exact(hir: &Hir) -> Option<Vec<String>> {
+    match hir.kind() {
+        HirKind::Empty => Some(vec![String::new()]),
+        HirKind::Literal(literal) => {
+            String::from_utf8(literal.0.to_vec()).ok().map(|literal| vec![literal])
+        },
+        HirKind::Class(Class::Unicode(class)) => {
+            let mut literals = Vec::new();
+
+            for range in class.ranges() {
+                let (start, end) = (range.start() as u32, range.end() as u32);
+
+                if (end - start) as usize >= MAX_ALTERNATIVES {
+                    return None;
+                }
+
+                for c in (start..=end).filter_map(char::from_u32) {
+                    literals.push(c.to_ascii_lowercase().to_string());
+                }
+            }
+
+            literals.sort_unstable();
+            literals.dedup();
+
+            if literals.len() > MAX_ALTERNATIVES {
+                None
+            } else {
+                Some(literals)
+            }
+        },
+        HirKind::Class(Class::Bytes(_)) | HirKind::Look(_) => None,
+        HirKind::Capture(capture) => exact(&capture.sub),
+        HirKind::Repetition(repetition) => {
+            if repetition.min == 1 && repetition.max == Some(1) {
+                exact(&repetition.sub)
+            } else {
+                None
+            }
+        },
+        HirKind::Concat(subs) => {
+            let mut literals = vec![String::new()];
+
+            for sub in subs {
+                literals = cross(&literals, &exact(sub)?)?;
+            }
+
+            Some(literals)
+        },
+        HirKind::Alternation(subs) => {
+            let mut literals = Vec::new();
+
+            for sub in subs {
+                literals.extend(exact(sub)?);
+            }
+
+            literals.sort_unstable();
+            literals.dedup();
+
+            if literals.len() > MAX_ALTERNATIVES {
+                None
+            } else {
+                Some(literals)
+            }
+        },
+    }
+}
+
+fn cross(prefixes: &[String], suffixes: &[String]) -> Option<Vec<String>> {
+    if prefixes.len() * suffixes.len() > MAX_ALTERNATIVES {
+        return None;
+    }
+
+    let mut literals = Vec::with_capacity(prefixes.len() * suffixes.len());
+
+    for prefix in prefixes {
+        for suffix in suffixes {
+            literals.push(format!("{prefix}{suffix}"));
+        }
+    }
+
+    literals.sort_unstable();
+    literals.dedup();
+
+    Some(literals)
+}
+
+/// Prefer sets whose shortest literal is longer, then sets with fewer literals.
+#[inline]
+fn score(literals: &[String]) -> (usize, isize) {
+    let shortest = literals.iter().map(|literal| literal.len()).min().unwrap_or(0);
+
+    (shortest, -(literals.len() as isize))
+}
@@ -0,0 +1,109 @@
+use yaml_rust::Yaml;
+
+use super::{fields, read_extensible_rules, Field, Regex, Template};
+use crate::{errors::RuleErrorKind, BotCategory, RuleError, RuleSection};
+
+#[derive(Debug)]
+pub struct BotRegex {
+    pub(crate) regex:            Regex,
+    pub(crate) category:         BotCategory,
+    pub(crate) name_replacement: Option<Template>,
+}
+
+impl BotRegex {
+    /// The fields built by this rule.
+    #[inline]
+    pub(crate) fn fields(&self) -> [Field<'_>; 1] {
+        fields!(self, ("name", 1, name_replacement),)
+    }
+
+    /// Read the rules of the section. Returns the rules and whether the built-in rules are kept after them.
+    pub fn from_yaml(yaml: &Yaml) -> Result<(Vec<BotRegex>, bool), RuleError> {
+        read_extensible_rules(yaml, RuleSection::Bot, |rule| {
+            let flags = rule.get_flags()?;
+            let regex = rule.require_regex("regex", flags)?;
+
+            let category = rule.require_str("bot_category")?;
+
+            let category = category.parse().map_err(|_| {
+                rule.error(Some("bot_category"), RuleErrorKind::InvalidValue(category.to_string()))
+            })?;
+
+            Ok(BotRegex {
+                name_replacement: rule.get_template("name_replacement", &regex)?,
+                category,
+                regex,
+            })
+        })
+    }
+
+    pub fn built_in_regexes() -> Vec<BotRegex> {
+        let rule = |regex: &str, category: BotCategory, name: Option<&str>| {
+            let regex = Regex::new(regex).unwrap();
+
+            BotRegex {
+                name_replacement: name.map(|name| Template::parse(name, &regex).unwrap()),
+                category,
+                regex,
+            }
+        };
+
+        vec![
+            // AI crawlers go first, because some of them, such as Bytespider and Applebot-Extended, look like the other crawlers
+            rule(
+                r"\b(GPTBot|ChatGPT-User|OAI-SearchBot|ClaudeBot|Claude-Web|Claude-User|anthropic-ai|CCBot|PerplexityBot|Perplexity-User|Bytespider|Amazonbot|cohere-ai|Meta-ExternalAgent|Meta-ExternalFetcher|Applebot-Extended|Google-Extended|Diffbot|YouBot|Timpibot)\b",
+                BotCategory::AICrawler,
+                None,
+            ),
+            rule(
+                r"\b(Googlebot(?:-Image|-News|-Video)?|Storebot-Google|Google-InspectionTool|AdsBot-Google|Mediapartners-Google|bingbot|BingPreview|DuckDuckBot|Baiduspider|YandexBot|YandexImages|Applebot|Sogou web spider|Exabot|SeznamBot|PetalBot|Qwantbot|Yeti|coccocbot)\b",
+                BotCategory::SearchCrawler,
+                None,
+            ),
+            rule(r"\bYahoo! (Slurp)\b", BotCategory::SearchCrawler, Some("Yahoo! Slurp")),
+            rule(
+                r"\b(AhrefsBot|AhrefsSiteAudit|SemrushBot|MJ12bot|DotBot|rogerbot|BLEXBot|DataForSeoBot|serpstatbot|SEOkicks|Screaming Frog SEO Spider|SiteAuditBot|barkrowler)\b",
+                BotCategory::SEOTool,
+                None,
+            ),
+            rule(
+                r"\b(UptimeRobot|Pingdom\.com_bot|StatusCake|Site24x7|Datadog Agent|DatadogSynthetics|NewRelicPinger|GTmetrix|Better Uptime Bot|Uptime-Kuma|Chrome-Lighthouse|check_http|Zabbix)\b",
+                BotCategory::Monitoring,
+                None,
+            ),
+            rule(
+                r"\b(Feedly|Feedfetcher-Google|FeedBurner|Feedbin|NewsBlur|Inoreader|Tiny Tiny RSS|The Old Reader|Miniflux|FreshRSS|NetNewsWire)\b",
+                BotCategory::FeedFetcher,
+                None,
+            ),
+            rule(r"\b(HeadlessChrome|PhantomJS|SlimerJS)\b", BotCategory::HeadlessBrowser, None),
+            rule(
+                r"^(curl|Wget|python-requests|Python-urllib|python-httpx|Go-http-client|okhttp|Java|Apache-HttpClient|node-fetch|axios|undici|libwww-perl|PostmanRuntime|Scrapy|GuzzleHttp|Ruby|Dart|reqwest)\b",
+                BotCategory::HTTPLibrary,
+                None,
+            ),
+            rule(r"\b(aiohttp)/", BotCategory::HTTPLibrary, None),
+            // the common tokens of the other bots
+            rule(
+                r"(?i)\b(?!cubot)([a-z][\w-]*?(?:bot|crawler|spider))\b",
+                BotCategory::Other,
+                None,
+            ),
+        ]
+    }
+}
+
+/// Find the contact URL in a user agent, such as `http://www.google.com/bot.html` in `(compatible; Googlebot/2.1; +http://www.google.com/bot.html)`.
+pub(crate) fn contact_url(user_agent: &str) -> Option<&str> {
+    let start = user_agent.match_indices("http").map(|(index, _)| index).find(|index| {
+        let rest = &user_agent[(index + 4)..];
+
+        rest.starts_with("://") || rest.starts_with("s://")
+    })?;
+
+    let url = &user_agent[start..];
+
+    let end = url.find(|c: char| c.is_whitespace() || c == ';' || c == ')').unwrap_or(url.len());
+
+    Some(&url[..end])
+}
@@ -1,12 +1,34 @@
-use onig::Regex;
+use yaml_rust::Yaml;
+
+use super::{fields, read_extensible_rules, Field, Regex, Template};
+use crate::{RuleError, RuleSection};
 
 #[derive(Debug)]
 pub struct CPURegex {
     pub(crate) regex:                    Regex,
-    pub(crate) architecture_replacement: Option<String>,
+    pub(crate) architecture_replacement: Option<Template>,
 }
 
 impl CPURegex {
+    /// The fields built by this rule.
+    #[inline]
+    pub(crate) fn fields(&self) -> [Field<'_>; 1] {
+        fields!(self, ("architecture", 1, architecture_replacement),)
+    }
+
+    /// Read the rules of the section. Returns the rules and whether the built-in rules are kept after them.
+    pub fn from_yaml(yaml: &Yaml) -> Result<(Vec<CPURegex>, bool), RuleError> {
+        read_extensible_rules(yaml, RuleSection::CPU, |rule| {
+            let flags = rule.get_flags()?;
+            let regex = rule.require_regex("regex", flags)?;
+
+            Ok(CPURegex {
+                architecture_replacement: rule.get_template("architecture_replacement", &regex)?,
+                regex,
+            })
+        })
+    }
+
     pub fn built_in_regexes() -> Vec<CPURegex> {
         vec![
             {
@@ -14,7 +36,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("amd64".to_string()),
+                    architecture_replacement: Some(Template::literal("amd64")),
                 }
             },
             {
@@ -22,7 +44,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("ia32".to_string()),
+                    architecture_replacement: Some(Template::literal("ia32")),
                 }
             },
             {
@@ -30,7 +52,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("ia32".to_string()),
+                    architecture_replacement: Some(Template::literal("ia32")),
                 }
             },
             {
@@ -38,7 +60,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("arm".to_string()),
+                    architecture_replacement: Some(Template::literal("arm")),
                 }
             },
             {
@@ -46,7 +68,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("ppc".to_string()),
+                    architecture_replacement: Some(Template::literal("ppc")),
                 }
             },
             {
@@ -54,7 +76,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("sparc".to_string()),
+                    architecture_replacement: Some(Template::literal("sparc")),
                 }
             },
             {
@@ -62,7 +84,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("ia64".to_string()),
+                    architecture_replacement: Some(Template::literal("ia64")),
                 }
             },
             {
@@ -70,7 +92,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("68k".to_string()),
+                    architecture_replacement: Some(Template::literal("68k")),
                 }
             },
             {
@@ -78,7 +100,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("arm".to_string()),
+                    architecture_replacement: Some(Template::literal("arm")),
                 }
             },
             {
@@ -86,7 +108,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("avr".to_string()),
+                    architecture_replacement: Some(Template::literal("avr")),
                 }
             },
             {
@@ -94,7 +116,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("irix".to_string()),
+                    architecture_replacement: Some(Template::literal("irix")),
                 }
             },
             {
@@ -102,7 +124,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("mips".to_string()),
+                    architecture_replacement: Some(Template::literal("mips")),
                 }
             },
             {
@@ -110,7 +132,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("sparc".to_string()),
+                    architecture_replacement: Some(Template::literal("sparc")),
                 }
             },
             {
@@ -118,7 +140,7 @@ impl CPURegex {
 
                 CPURegex {
                     regex,
-                    architecture_replacement: Some("pa-risc".to_string()),
+                    architecture_replacement: Some(Template::literal("pa-risc")),
                 }
             },
         ]
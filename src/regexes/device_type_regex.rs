@@ -0,0 +1,129 @@
+use yaml_rust::Yaml;
+
+use super::{read_extensible_rules, Regex};
+use crate::{errors::RuleErrorKind, DeviceType, RuleError, RuleSection};
+
+/// A rule to classify the device type. All of its regular expressions which exist need to match.
+#[derive(Debug)]
+pub struct DeviceTypeRegex {
+    /// Matches the user agent.
+    pub(crate) regex:        Option<Regex>,
+    /// Matches the parsed device name.
+    pub(crate) device_regex: Option<Regex>,
+    /// Matches the parsed OS name.
+    pub(crate) os_regex:     Option<Regex>,
+    pub(crate) device_type:  DeviceType,
+}
+
+impl DeviceTypeRegex {
+    /// Read the rules of the section. Returns the rules and whether the built-in rules are kept after them.
+    pub fn from_yaml(yaml: &Yaml) -> Result<(Vec<DeviceTypeRegex>, bool), RuleError> {
+        read_extensible_rules(yaml, RuleSection::DeviceType, |rule| {
+            let flags = rule.get_flags()?;
+
+            let regex = rule.get_regex("regex", flags)?;
+            let device_regex = rule.get_regex("device_regex", flags)?;
+            let os_regex = rule.get_regex("os_regex", flags)?;
+
+            if regex.is_none() && device_regex.is_none() && os_regex.is_none() {
+                return Err(rule.error(Some("regex"), RuleErrorKind::MissingKey));
+            }
+
+            let device_type = rule.require_str("device_type")?;
+
+            let device_type = device_type.parse::<DeviceType>().map_err(|_| {
+                rule.error(
+                    Some("device_type"),
+                    RuleErrorKind::InvalidValue(device_type.to_string()),
+                )
+            })?;
+
+            Ok(DeviceTypeRegex {
+                regex,
+                device_regex,
+                os_regex,
+                device_type,
+            })
+        })
+    }
+
+    pub fn built_in_regexes() -> Vec<DeviceTypeRegex> {
+        let rule = |regex: Option<&str>,
+                    device_regex: Option<&str>,
+                    os_regex: Option<&str>,
+                    device_type: DeviceType| DeviceTypeRegex {
+            regex: regex.map(|regex| Regex::new(regex).unwrap()),
+            device_regex: device_regex.map(|regex| Regex::new(regex).unwrap()),
+            os_regex: os_regex.map(|regex| Regex::new(regex).unwrap()),
+            device_type,
+        };
+
+        vec![
+            rule(None, Some(r"^Spider$"), None, DeviceType::Bot),
+            rule(
+                Some(r"(?i)(?<!cu)bot\b|crawl|spider|slurp|archiver|facebookexternalhit|headless"),
+                None,
+                None,
+                DeviceType::Bot,
+            ),
+            rule(
+                Some(r"(?i)playstation|xbox|nintendo|\bwii\b|ouya|shield android tv"),
+                None,
+                None,
+                DeviceType::Console,
+            ),
+            rule(
+                Some(
+                    r"(?i)smart-?tv|google ?tv|apple ?tv|android tv|hbbtv|crkey|roku|bravia|netcast|web0s|webos.+tv|tizen.+tv|\btv\b|\bAFT[A-Z]",
+                ),
+                None,
+                None,
+                DeviceType::TV,
+            ),
+            rule(
+                Some(r"(?i)watch ?os|smart ?watch|wear ?os|\bglass\b"),
+                None,
+                None,
+                DeviceType::Wearable,
+            ),
+            rule(None, Some(r"(?i)watch"), None, DeviceType::Wearable),
+            rule(
+                Some(r"(?i)ipad|tablet|kindle|\bsilk\b|playbook|\btab\b"),
+                None,
+                None,
+                DeviceType::Tablet,
+            ),
+            rule(None, Some(r"(?i)ipad|tablet|kindle|galaxy tab"), None, DeviceType::Tablet),
+            // Android devices without the `Mobile` token are usually tablets
+            rule(Some(r"^(?:(?!Mobile).)*$"), None, Some(r"^Android$"), DeviceType::Tablet),
+            rule(None, Some(r"(?i)iphone|ipod|smartphone|feature phone"), None, DeviceType::Mobile),
+            rule(
+                Some(r"(?i)mobile|phone|blackberry|opera mini|iemobile|symbian|j2me|midp"),
+                None,
+                None,
+                DeviceType::Mobile,
+            ),
+            rule(
+                None,
+                None,
+                Some(r"^(?:iOS|Android|Windows Phone|BlackBerry OS|Symbian OS|KaiOS|Firefox OS)$"),
+                DeviceType::Mobile,
+            ),
+            rule(
+                None,
+                None,
+                Some(
+                    r"^(?:Windows|Mac OS X|Chrome OS|Linux|Ubuntu|Kubuntu|Debian|Fedora|Red Hat|CentOS|SUSE|openSUSE|Gentoo|Slackware|Mandriva|Mint|Arch Linux|FreeBSD|OpenBSD|NetBSD|Solaris)$",
+                ),
+                DeviceType::Desktop,
+            ),
+        ]
+    }
+
+    /// Whether this rule matches the user agent and its parsed device and OS names.
+    pub(crate) fn is_match(&self, user_agent: &str, device_name: &str, os_name: &str) -> bool {
+        self.regex.as_ref().map_or(true, |regex| regex.is_match(user_agent))
+            && self.device_regex.as_ref().map_or(true, |regex| regex.is_match(device_name))
+            && self.os_regex.as_ref().map_or(true, |regex| regex.is_match(os_name))
+    }
+}
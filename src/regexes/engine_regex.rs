@@ -1,15 +1,46 @@
-use onig::Regex;
+use yaml_rust::Yaml;
+
+use super::{fields, read_extensible_rules, Field, Regex, Template};
+use crate::{RuleError, RuleSection};
 
 #[derive(Debug)]
 pub struct EngineRegex {
     pub(crate) regex:                 Regex,
-    pub(crate) name_replacement:      Option<String>,
-    pub(crate) engine_v1_replacement: Option<String>,
-    pub(crate) engine_v2_replacement: Option<String>,
-    pub(crate) engine_v3_replacement: Option<String>,
+    pub(crate) engine_replacement:    Option<Template>,
+    pub(crate) engine_v1_replacement: Option<Template>,
+    pub(crate) engine_v2_replacement: Option<Template>,
+    pub(crate) engine_v3_replacement: Option<Template>,
 }
 
 impl EngineRegex {
+    /// The fields built by this rule.
+    #[inline]
+    pub(crate) fn fields(&self) -> [Field<'_>; 4] {
+        fields!(
+            self,
+            ("name", 1, engine_replacement),
+            ("major", 2, engine_v1_replacement),
+            ("minor", 3, engine_v2_replacement),
+            ("patch", 4, engine_v3_replacement),
+        )
+    }
+
+    /// Read the rules of the section. Returns the rules and whether the built-in rules are kept after them.
+    pub fn from_yaml(yaml: &Yaml) -> Result<(Vec<EngineRegex>, bool), RuleError> {
+        read_extensible_rules(yaml, RuleSection::Engine, |rule| {
+            let flags = rule.get_flags()?;
+            let regex = rule.require_regex("regex", flags)?;
+
+            Ok(EngineRegex {
+                engine_replacement: rule.get_template("engine_replacement", &regex)?,
+                engine_v1_replacement: rule.get_template("engine_v1_replacement", &regex)?,
+                engine_v2_replacement: rule.get_template("engine_v2_replacement", &regex)?,
+                engine_v3_replacement: rule.get_template("engine_v3_replacement", &regex)?,
+                regex,
+            })
+        })
+    }
+
     pub fn built_in_regexes() -> Vec<EngineRegex> {
         vec![
             {
@@ -18,7 +49,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("EdgeHTML".to_string()),
+                    engine_replacement: Some(Template::literal("EdgeHTML")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -29,7 +60,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Blink".to_string()),
+                    engine_replacement: Some(Template::literal("Blink")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -40,7 +71,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Presto".to_string()),
+                    engine_replacement: Some(Template::literal("Presto")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -51,7 +82,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("WebKit".to_string()),
+                    engine_replacement: Some(Template::literal("WebKit")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -62,7 +93,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Trident".to_string()),
+                    engine_replacement: Some(Template::literal("Trident")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -73,7 +104,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("NetFront".to_string()),
+                    engine_replacement: Some(Template::literal("NetFront")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -84,7 +115,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("NetSurf".to_string()),
+                    engine_replacement: Some(Template::literal("NetSurf")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -95,7 +126,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Amaya".to_string()),
+                    engine_replacement: Some(Template::literal("Amaya")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -106,7 +137,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Lynx".to_string()),
+                    engine_replacement: Some(Template::literal("Lynx")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -117,7 +148,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("w3m".to_string()),
+                    engine_replacement: Some(Template::literal("w3m")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -128,7 +159,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Goanna".to_string()),
+                    engine_replacement: Some(Template::literal("Goanna")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -140,7 +171,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("KHTML".to_string()),
+                    engine_replacement: Some(Template::literal("KHTML")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -152,7 +183,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Tasman".to_string()),
+                    engine_replacement: Some(Template::literal("Tasman")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -164,7 +195,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Links".to_string()),
+                    engine_replacement: Some(Template::literal("Links")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -175,7 +206,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("iCab".to_string()),
+                    engine_replacement: Some(Template::literal("iCab")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -188,7 +219,7 @@ impl EngineRegex {
 
                 EngineRegex {
                     regex,
-                    name_replacement: Some("Gecko".to_string()),
+                    engine_replacement: Some(Template::literal("Gecko")),
                     engine_v1_replacement: None,
                     engine_v2_replacement: None,
                     engine_v3_replacement: None,
@@ -1,11 +1,47 @@
+mod bot_regex;
 mod cpu_regex;
 mod device_regex;
+mod device_type_regex;
 mod engine_regex;
 mod os_regex;
 mod product_regex;
+mod regex;
+mod rule_yaml;
+mod template;
 
+pub(crate) use bot_regex::contact_url;
+pub use bot_regex::BotRegex;
 pub use cpu_regex::CPURegex;
 pub use device_regex::DeviceRegex;
+pub use device_type_regex::DeviceTypeRegex;
 pub use engine_regex::EngineRegex;
 pub use os_regex::OSRegex;
 pub use product_regex::ProductRegex;
+pub(crate) use regex::{Captures, Regex, RegexFlags};
+pub(crate) use rule_yaml::{read_extensible_rules, read_rules};
+pub(crate) use template::Template;
+
+/// A field of a model which is built by a rule, from its replacement or from the capture group.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Field<'a> {
+    /// The name of the field of the model, such as `name`.
+    pub(crate) name:        &'static str,
+    /// The key of the replacement in YAML data, such as `family_replacement`.
+    pub(crate) key:         &'static str,
+    /// The capture group used when there is no replacement.
+    pub(crate) group:       usize,
+    pub(crate) replacement: Option<&'a Template>,
+}
+
+macro_rules! fields {
+    ($self:ident, $(($name:literal, $group:literal, $key:ident)),+ $(,)?) => {
+        [$(crate::regexes::Field {
+            name:        $name,
+            key:         stringify!($key),
+            group:       $group,
+            replacement: $self.$key.as_ref(),
+        }),+]
+    };
+}
+
+pub(crate) use fields;
@@ -1,99 +1,45 @@
-use onig::Regex;
 use yaml_rust::Yaml;
 
-use crate::UserAgentParserError;
+use super::{fields, read_rules, Field, Regex, Template};
+use crate::{RuleError, RuleSection};
 
 #[derive(Debug)]
 pub struct OSRegex {
     pub(crate) regex:             Regex,
-    pub(crate) os_replacement:    Option<String>,
-    pub(crate) os_v1_replacement: Option<String>,
-    pub(crate) os_v2_replacement: Option<String>,
-    pub(crate) os_v3_replacement: Option<String>,
-    pub(crate) os_v4_replacement: Option<String>,
+    pub(crate) os_replacement:    Option<Template>,
+    pub(crate) os_v1_replacement: Option<Template>,
+    pub(crate) os_v2_replacement: Option<Template>,
+    pub(crate) os_v3_replacement: Option<Template>,
+    pub(crate) os_v4_replacement: Option<Template>,
 }
 
 impl OSRegex {
-    pub fn from_yaml(yaml: &Yaml) -> Result<Vec<OSRegex>, UserAgentParserError> {
-        let yamls = yaml.as_vec().ok_or(UserAgentParserError::IncorrectSource)?;
-
-        let yamls_len = yamls.len();
-
-        if yamls_len == 0 {
-            Err(UserAgentParserError::IncorrectSource)
-        } else {
-            let mut os_regexes = Vec::with_capacity(yamls_len);
-
-            let yaml_regex = Yaml::String("regex".to_string());
-            let yaml_os_replacement = Yaml::String("os_replacement".to_string());
-            let yaml_os_v1_replacement = Yaml::String("os_v1_replacement".to_string());
-            let yaml_os_v2_replacement = Yaml::String("os_v2_replacement".to_string());
-            let yaml_os_v3_replacement = Yaml::String("os_v3_replacement".to_string());
-            let yaml_os_v4_replacement = Yaml::String("os_v4_replacement".to_string());
-
-            for yaml in yamls {
-                let yaml = yaml.as_hash().ok_or(UserAgentParserError::IncorrectSource)?;
-
-                let regex = Regex::new(
-                    yaml.get(&yaml_regex)
-                        .ok_or(UserAgentParserError::IncorrectSource)?
-                        .as_str()
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                )?;
-
-                let os_replacement = match yaml.get(&yaml_os_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let os_v1_replacement = match yaml.get(&yaml_os_v1_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let os_v2_replacement = match yaml.get(&yaml_os_v2_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let os_v3_replacement = match yaml.get(&yaml_os_v3_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let os_v4_replacement = match yaml.get(&yaml_os_v4_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let os_regex = OSRegex {
-                    regex,
-                    os_replacement,
-                    os_v1_replacement,
-                    os_v2_replacement,
-                    os_v3_replacement,
-                    os_v4_replacement,
-                };
-
-                os_regexes.push(os_regex);
-            }
+    /// The fields built by this rule.
+    #[inline]
+    pub(crate) fn fields(&self) -> [Field<'_>; 5] {
+        fields!(
+            self,
+            ("name", 1, os_replacement),
+            ("major", 2, os_v1_replacement),
+            ("minor", 3, os_v2_replacement),
+            ("patch", 4, os_v3_replacement),
+            ("patch_minor", 5, os_v4_replacement),
+        )
+    }
 
-            Ok(os_regexes)
-        }
+    pub fn from_yaml(yaml: &Yaml) -> Result<Vec<OSRegex>, RuleError> {
+        read_rules(yaml, RuleSection::OS, |rule| {
+            let flags = rule.get_flags()?;
+            let regex = rule.require_regex("regex", flags)?;
+
+            Ok(OSRegex {
+                os_replacement: rule.get_template("os_replacement", &regex)?,
+                os_v1_replacement: rule.get_template("os_v1_replacement", &regex)?,
+                os_v2_replacement: rule.get_template("os_v2_replacement", &regex)?,
+                os_v3_replacement: rule.get_template("os_v3_replacement", &regex)?,
+                os_v4_replacement: rule.get_template("os_v4_replacement", &regex)?,
+                regex,
+            })
+        })
     }
 }
@@ -1,88 +1,42 @@
-use onig::Regex;
 use yaml_rust::Yaml;
 
-use crate::UserAgentParserError;
+use super::{fields, read_rules, Field, Regex, Template};
+use crate::{RuleError, RuleSection};
 
 #[derive(Debug)]
 pub struct ProductRegex {
     pub(crate) regex:              Regex,
-    pub(crate) family_replacement: Option<String>,
-    pub(crate) v1_replacement:     Option<String>,
-    pub(crate) v2_replacement:     Option<String>,
-    pub(crate) v3_replacement:     Option<String>,
+    pub(crate) family_replacement: Option<Template>,
+    pub(crate) v1_replacement:     Option<Template>,
+    pub(crate) v2_replacement:     Option<Template>,
+    pub(crate) v3_replacement:     Option<Template>,
 }
 
 impl ProductRegex {
-    pub fn from_yaml(yaml: &Yaml) -> Result<Vec<ProductRegex>, UserAgentParserError> {
-        let yamls = yaml.as_vec().ok_or(UserAgentParserError::IncorrectSource)?;
-
-        let yamls_len = yamls.len();
-
-        if yamls_len == 0 {
-            Err(UserAgentParserError::IncorrectSource)
-        } else {
-            let mut user_agent_regexes = Vec::with_capacity(yamls_len);
-
-            let yaml_regex = Yaml::String("regex".to_string());
-            let yaml_family_replacement = Yaml::String("family_replacement".to_string());
-            let yaml_v1_replacement = Yaml::String("v1_replacement".to_string());
-            let yaml_v2_replacement = Yaml::String("v2_replacement".to_string());
-            let yaml_v3_replacement = Yaml::String("v3_replacement".to_string());
-
-            for yaml in yamls {
-                let yaml = yaml.as_hash().ok_or(UserAgentParserError::IncorrectSource)?;
-
-                let regex = Regex::new(
-                    yaml.get(&yaml_regex)
-                        .ok_or(UserAgentParserError::IncorrectSource)?
-                        .as_str()
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                )?;
-
-                let family_replacement = match yaml.get(&yaml_family_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let v1_replacement = match yaml.get(&yaml_v1_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let v2_replacement = match yaml.get(&yaml_v2_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let v3_replacement = match yaml.get(&yaml_v3_replacement) {
-                    Some(yaml) => yaml
-                        .as_str()
-                        .map(|s| Some(s.to_string()))
-                        .ok_or(UserAgentParserError::IncorrectSource)?,
-                    None => None,
-                };
-
-                let user_agent_regex = ProductRegex {
-                    regex,
-                    family_replacement,
-                    v1_replacement,
-                    v2_replacement,
-                    v3_replacement,
-                };
-
-                user_agent_regexes.push(user_agent_regex);
-            }
+    /// The fields built by this rule.
+    #[inline]
+    pub(crate) fn fields(&self) -> [Field<'_>; 4] {
+        fields!(
+            self,
+            ("name", 1, family_replacement),
+            ("major", 2, v1_replacement),
+            ("minor", 3, v2_replacement),
+            ("patch", 4, v3_replacement),
+        )
+    }
 
-            Ok(user_agent_regexes)
-        }
+    pub fn from_yaml(yaml: &Yaml) -> Result<Vec<ProductRegex>, RuleError> {
+        read_rules(yaml, RuleSection::Product, |rule| {
+            let flags = rule.get_flags()?;
+            let regex = rule.require_regex("regex", flags)?;
+
+            Ok(ProductRegex {
+                family_replacement: rule.get_template("family_replacement", &regex)?,
+                v1_replacement: rule.get_template("v1_replacement", &regex)?,
+                v2_replacement: rule.get_template("v2_replacement", &regex)?,
+                v3_replacement: rule.get_template("v3_replacement", &regex)?,
+                regex,
+            })
+        })
     }
 }
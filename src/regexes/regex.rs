@@ -0,0 +1,286 @@
+#[cfg(not(any(feature = "onig", feature = "pure-rust")))]
+compile_error!("either the `onig` feature or the `pure-rust` feature must be enabled");
+
+use self::backend::{Inner, InnerCaptures};
+use crate::errors::RegexError;
+
+/// The options of a regular expression, which are set by the `regex_flag` key of a rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub(crate) struct RegexFlags {
+    /// `i`: letters match both upper and lower cases.
+    pub(crate) ignore_case: bool,
+    /// `m`: `^` and `$` match at the start and the end of every line.
+    pub(crate) multi_line:  bool,
+    /// `x`: whitespace and `#` comments in the pattern are ignored.
+    pub(crate) extended:    bool,
+}
+
+impl RegexFlags {
+    /// Parse flags such as `i` or `ix`. Returns `None` if there is an unknown flag.
+    pub(crate) fn parse(flags: &str) -> Option<RegexFlags> {
+        let mut regex_flags = RegexFlags::default();
+
+        for flag in flags.chars() {
+            match flag {
+                'i' => regex_flags.ignore_case = true,
+                'm' => regex_flags.multi_line = true,
+                'x' => regex_flags.extended = true,
+                _ => return None,
+            }
+        }
+
+        Some(regex_flags)
+    }
+}
+
+/// A compiled regular expression of the selected backend, which also keeps its source.
+#[derive(Debug)]
+pub(crate) struct Regex {
+    source: String,
+    flags:  RegexFlags,
+    inner:  Inner,
+}
+
+/// Capture groups of a successful match.
+pub(crate) struct Captures<'t> {
+    inner: InnerCaptures<'t>,
+}
+
+impl Regex {
+    #[inline]
+    pub(crate) fn new(source: &str) -> Result<Regex, RegexError> {
+        Self::with_flags(source, RegexFlags::default())
+    }
+
+    pub(crate) fn with_flags(source: &str, flags: RegexFlags) -> Result<Regex, RegexError> {
+        let inner = Inner::new(source, flags)?;
+
+        Ok(Regex {
+            source: source.to_string(),
+            flags,
+            inner,
+        })
+    }
+
+    /// The source of this regular expression.
+    #[inline]
+    pub(crate) fn as_str(&self) -> &str {
+        self.source.as_str()
+    }
+
+    #[inline]
+    pub(crate) fn flags(&self) -> RegexFlags {
+        self.flags
+    }
+
+    /// The number of capture groups, including the whole match.
+    #[inline]
+    pub(crate) fn captures_len(&self) -> usize {
+        self.inner.captures_len()
+    }
+
+    /// The index of the capture group with the name.
+    #[inline]
+    pub(crate) fn capture_index(&self, name: &str) -> Option<usize> {
+        self.inner.capture_index(name)
+    }
+
+    #[inline]
+    pub(crate) fn is_match(&self, text: &str) -> bool {
+        self.inner.is_match(text)
+    }
+
+    #[inline]
+    pub(crate) fn captures<'t>(&self, text: &'t str) -> Option<Captures<'t>> {
+        self.inner.captures(text).map(|inner| Captures {
+            inner,
+        })
+    }
+}
+
+impl<'t> Captures<'t> {
+    /// The number of groups, including the whole match.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// The text of the group at the index, if it participated in the match.
+    #[inline]
+    pub(crate) fn at(&self, index: usize) -> Option<&'t str> {
+        self.inner.at(index)
+    }
+}
+
+#[cfg(all(feature = "onig", not(feature = "pure-rust")))]
+mod backend {
+    use onig::{RegexOptions, Syntax};
+
+    use super::RegexFlags;
+    use crate::errors::RegexError;
+
+    #[derive(Debug)]
+    pub(super) struct Inner(onig::Regex);
+
+    pub(super) struct InnerCaptures<'t>(onig::Captures<'t>);
+
+    impl Inner {
+        pub(super) fn new(source: &str, flags: RegexFlags) -> Result<Inner, RegexError> {
+            // plain groups keep capturing in a pattern with named groups, as they do on the other backend
+            let mut regex_options = RegexOptions::REGEX_OPTION_CAPTURE_GROUP;
+
+            if flags.ignore_case {
+                regex_options |= RegexOptions::REGEX_OPTION_IGNORECASE;
+            }
+
+            // `^` and `$` of the Ruby syntax match at line boundaries unless they are turned into `\A` and `\Z`, like the other backend
+            if !flags.multi_line {
+                regex_options |= RegexOptions::REGEX_OPTION_SINGLELINE;
+            }
+
+            if flags.extended {
+                regex_options |= RegexOptions::REGEX_OPTION_EXTEND;
+            }
+
+            Ok(Inner(onig::Regex::with_options(source, regex_options, Syntax::default())?))
+        }
+
+        #[inline]
+        pub(super) fn captures_len(&self) -> usize {
+            self.0.captures_len() + 1
+        }
+
+        pub(super) fn capture_index(&self, name: &str) -> Option<usize> {
+            let mut index = None;
+
+            self.0.foreach_name(|group_name, indices| {
+                if group_name == name {
+                    index = indices.first().map(|&i| i as usize);
+
+                    false
+                } else {
+                    true
+                }
+            });
+
+            index
+        }
+
+        #[inline]
+        pub(super) fn is_match(&self, text: &str) -> bool {
+            self.0.find(text).is_some()
+        }
+
+        #[inline]
+        pub(super) fn captures<'t>(&self, text: &'t str) -> Option<InnerCaptures<'t>> {
+            self.0.captures(text).map(InnerCaptures)
+        }
+    }
+
+    impl<'t> InnerCaptures<'t> {
+        #[inline]
+        pub(super) fn len(&self) -> usize {
+            self.0.len()
+        }
+
+        #[inline]
+        pub(super) fn at(&self, index: usize) -> Option<&'t str> {
+            self.0.at(index)
+        }
+    }
+}
+
+#[cfg(feature = "pure-rust")]
+mod backend {
+    use super::RegexFlags;
+    use crate::errors::RegexError;
+
+    /// Rules without lookaround or backreferences are compiled by the `regex` crate. The others fall back to the backtracking engine of the `fancy-regex` crate.
+    #[derive(Debug)]
+    pub(super) enum Inner {
+        Plain(regex::Regex),
+        Fancy(fancy_regex::Regex),
+    }
+
+    pub(super) enum InnerCaptures<'t> {
+        Plain(regex::Captures<'t>),
+        Fancy(fancy_regex::Captures<'t>),
+    }
+
+    impl Inner {
+        pub(super) fn new(source: &str, flags: RegexFlags) -> Result<Inner, RegexError> {
+            let mut prefix = String::new();
+
+            if flags.ignore_case {
+                prefix.push('i');
+            }
+
+            if flags.multi_line {
+                prefix.push('m');
+            }
+
+            if flags.extended {
+                prefix.push('x');
+            }
+
+            let source =
+                if prefix.is_empty() { source.to_string() } else { format!("(?{prefix}){source}") };
+
+            match regex::Regex::new(&source) {
+                Ok(regex) => Ok(Inner::Plain(regex)),
+                Err(_) => Ok(Inner::Fancy(fancy_regex::Regex::new(&source).map_err(Box::new)?)),
+            }
+        }
+
+        #[inline]
+        pub(super) fn captures_len(&self) -> usize {
+            match self {
+                Inner::Plain(regex) => regex.captures_len(),
+                Inner::Fancy(regex) => regex.captures_len(),
+            }
+        }
+
+        #[inline]
+        pub(super) fn capture_index(&self, name: &str) -> Option<usize> {
+            match self {
+                Inner::Plain(regex) => regex.capture_names().position(|n| n == Some(name)),
+                Inner::Fancy(regex) => regex.capture_names().position(|n| n == Some(name)),
+            }
+        }
+
+        #[inline]
+        pub(super) fn is_match(&self, text: &str) -> bool {
+            match self {
+                Inner::Plain(regex) => regex.is_match(text),
+                Inner::Fancy(regex) => regex.is_match(text).unwrap_or(false),
+            }
+        }
+
+        #[inline]
+        pub(super) fn captures<'t>(&self, text: &'t str) -> Option<InnerCaptures<'t>> {
+            match self {
+                Inner::Plain(regex) => regex.captures(text).map(InnerCaptures::Plain),
+                // a backtracking limit error is treated as no match
+                Inner::Fancy(regex) => regex.captures(text).ok()?.map(InnerCaptures::Fancy),
+            }
+        }
+    }
+
+    impl<'t> InnerCaptures<'t> {
+        #[inline]
+        pub(super) fn len(&self) -> usize {
+            match self {
+                InnerCaptures::Plain(captures) => captures.len(),
+                InnerCaptures::Fancy(captures) => captures.len(),
+            }
+        }
+
+        #[inline]
+        pub(super) fn at(&self, index: usize) -> Option<&'t str> {
+            match self {
+                InnerCaptures::Plain(captures) => captures.get(index).map(|m| m.as_str()),
+                InnerCaptures::Fancy(captures) => captures.get(index).map(|m| m.as_str()),
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use yaml_rust::{
+    parser::{Event, MarkedEventReceiver, Parser},
+    scanner::Marker,
+    yaml::Hash,
+    Yaml,
+};
+
+use super::{Regex, RegexFlags, Template};
+use crate::{errors::RuleErrorKind, RuleError, RuleSection};
+
+/// A rule in YAML data which is being read.
+pub(crate) struct RuleYaml<'a> {
+    section: RuleSection,
+    index:   usize,
+    hash:    &'a Hash,
+    regex:   Option<&'a str>,
+}
+
+/// Read every rule of a section, which needs to be a non-empty sequence of mappings.
+pub(crate) fn read_rules<'a, T, F>(
+    yaml: &'a Yaml,
+    section: RuleSection,
+    mut f: F,
+) -> Result<Vec<T>, RuleError>
+where
+    F: FnMut(&mut RuleYaml<'a>) -> Result<T, RuleError>, {
+    let yamls = match yaml.as_vec() {
+        Some(yamls) if !yamls.is_empty() => yamls,
+        _ => {
+            return Err(RuleError {
+                section,
+                index: None,
+                key: None,
+                regex: None,
+                position: None,
+                kind: RuleErrorKind::NotASequence,
+            })
+        },
+    };
+
+    let mut rules = Vec::with_capacity(yamls.len());
+
+    for (index, yaml) in yamls.iter().enumerate() {
+        let hash = yaml.as_hash().ok_or(RuleError {
+            section,
+            index: Some(index),
+            key: None,
+            regex: None,
+            position: None,
+            kind: RuleErrorKind::NotAMapping,
+        })?;
+
+        let mut rule = RuleYaml {
+            section,
+            index,
+            hash,
+            regex: None,
+        };
+
+        rules.push(f(&mut rule)?);
+    }
+
+    Ok(rules)
+}
+
+/// Read a section which has built-in rules. The section is either a sequence of rules, which are tried before the built-in rules, or a mapping of `rules` and `built_in`. Setting `built_in` to `false` replaces the built-in rules.
+///
+/// Returns the rules and whether the built-in rules are kept.
+pub(crate) fn read_extensible_rules<'a, T, F>(
+    yaml: &'a Yaml,
+    section: RuleSection,
+    f: F,
+) -> Result<(Vec<T>, bool), RuleError>
+where
+    F: FnMut(&mut RuleYaml<'a>) -> Result<T, RuleError>, {
+    match yaml.as_hash() {
+        Some(hash) => {
+            let built_in = match hash.get(&Yaml::String("built_in".to_string())) {
+                Some(yaml) => yaml.as_bool().ok_or(RuleError {
+                    section,
+                    index: None,
+                    key: Some("built_in"),
+                    regex: None,
+                    position: None,
+                    kind: RuleErrorKind::NotABoolean,
+                })?,
+                None => true,
+            };
+
+            let rules = match hash.get(&Yaml::String("rules".to_string())) {
+                Some(yaml) => read_rules(yaml, section, f)?,
+                None => Vec::new(),
+            };
+
+            Ok((rules, built_in))
+        },
+        None => Ok((read_rules(yaml, section, f)?, true)),
+    }
+}
+
+impl<'a> RuleYaml<'a> {
+    /// Create an error of this rule.
+    pub(crate) fn error(&self, key: Option<&'static str>, kind: RuleErrorKind) -> RuleError {
+        RuleError {
+            section: self.section,
+            index: Some(self.index),
+            key,
+            regex: self.regex.map(|regex| regex.to_string()),
+            position: None,
+            kind,
+        }
+    }
+
+    /// Get the string value of a key if the key exists.
+    pub(crate) fn get_str(&self, key: &'static str) -> Result<Option<&'a str>, RuleError> {
+        match self.hash.get(&Yaml::String(key.to_string())) {
+            Some(yaml) => match yaml.as_str() {
+                Some(s) => Ok(Some(s)),
+                None => Err(self.error(Some(key), RuleErrorKind::NotAString)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Get the string value of a key which is required.
+    pub(crate) fn require_str(&self, key: &'static str) -> Result<&'a str, RuleError> {
+        self.get_str(key)?.ok_or_else(|| self.error(Some(key), RuleErrorKind::MissingKey))
+    }
+
+    /// Compile the replacement template of a key if the key exists.
+    pub(crate) fn get_template(
+        &self,
+        key: &'static str,
+        regex: &Regex,
+    ) -> Result<Option<Template>, RuleError> {
+        match self.get_str(key)? {
+            Some(source) => match Template::parse(source, regex) {
+                Ok(template) => Ok(Some(template)),
+                Err(kind) => Err(self.error(Some(key), kind)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Get the flags of the regexes of this rule from the `regex_flag` key.
+    pub(crate) fn get_flags(&self) -> Result<RegexFlags, RuleError> {
+        match self.get_str("regex_flag")? {
+            Some(flags) => RegexFlags::parse(flags).ok_or_else(|| {
+                self.error(Some("regex_flag"), RuleErrorKind::InvalidValue(flags.to_string()))
+            }),
+            None => Ok(RegexFlags::default()),
+        }
+    }
+
+    /// Compile a regex of a key if the key exists. The source is kept for the later errors of this rule.
+    pub(crate) fn get_regex(
+        &mut self,
+        key: &'static str,
+        flags: RegexFlags,
+    ) -> Result<Option<Regex>, RuleError> {
+        match self.get_str(key)? {
+            Some(source) => {
+                if self.regex.is_none() {
+                    self.regex = Some(source);
+                }
+
+                match Regex::with_flags(source, flags) {
+                    Ok(regex) => Ok(Some(regex)),
+                    Err(error) => {
+                        let mut error = self.error(Some(key), RuleErrorKind::Regex(error));
+
+                        error.regex = Some(source.to_string());
+
+                        Err(error)
+                    },
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Compile a regex of a key which is required.
+    #[inline]
+    pub(crate) fn require_regex(
+        &mut self,
+        key: &'static str,
+        flags: RegexFlags,
+    ) -> Result<Regex, RuleError> {
+        self.get_regex(key, flags)?.ok_or_else(|| self.error(Some(key), RuleErrorKind::MissingKey))
+    }
+}
+
+impl RuleError {
+    /// Find the line and the column of this error in YAML data.
+    pub(crate) fn locate(mut self, yaml: &str) -> RuleError {
+        let mut locator = Locator {
+            section: self.section.as_str(),
+            index:   self.index,
+            key:     self.key,
+            frames:  Vec::new(),
+            found:   [None; 3],
+            pending: None,
+        };
+
+        // the YAML data has been loaded successfully, so the errors can be ignored
+        let _ = Parser::new(yaml.chars()).load(&mut locator, false);
+
+        self.position = locator
+            .found
+            .iter()
+            .rev()
+            .find_map(|marker| marker.map(|marker| (marker.line(), marker.col() + 1)));
+
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug)]
+enum Frame {
+    /// The pending key. `None` means that the next node is a key.
+    Mapping(Option<String>),
+    /// The index of the next item.
+    Sequence(usize),
+}
+
+/// Finds the markers of a section, a rule in the section, and a key of the rule.
+struct Locator<'a> {
+    section: &'a str,
+    index:   Option<usize>,
+    key:     Option<&'a str>,
+    frames:  Vec<(Frame, Option<Segment>)>,
+    found:   [Option<Marker>; 3],
+    /// The found mapping whose marker is to be replaced with the one of its first key, because the marker of a block mapping is after the first key.
+    pending: Option<usize>,
+}
+
+impl<'a> Locator<'a> {
+    /// The segment of the next node in its parent. `None` for the root node or a key.
+    fn next_segment(&self) -> Option<Segment> {
+        match self.frames.last() {
+            Some((Frame::Mapping(Some(key)), _)) => Some(Segment::Key(key.clone())),
+            Some((Frame::Sequence(index), _)) => Some(Segment::Index(*index)),
+            _ => None,
+        }
+    }
+
+    /// Whether the path of the containers, plus the next segment, is the section with the indices. The `rules` key of a section which is a mapping is skipped.
+    fn is_at(&self, segment: &Option<Segment>, depth: usize) -> bool {
+        let mut path = self
+            .frames
+            .iter()
+            .filter_map(|(_, segment)| segment.as_ref())
+            .chain(segment)
+            .peekable();
+
+        if path.next() != Some(&Segment::Key(self.section.to_string())) {
+            return false;
+        }
+
+        if path.peek() == Some(&&Segment::Key(String::from("rules"))) {
+            path.next();
+        }
+
+        match depth {
+            0 => path.next().is_none(),
+            _ => {
+                self.index.is_some()
+                    && path.next() == self.index.map(Segment::Index).as_ref()
+                    && path.next().is_none()
+            },
+        }
+    }
+
+    /// Finish a node in its parent.
+    fn finish_node(&mut self) {
+        match self.frames.last_mut() {
+            Some((Frame::Mapping(key), _)) => *key = None,
+            Some((Frame::Sequence(index), _)) => *index += 1,
+            None => (),
+        }
+    }
+}
+
+impl<'a> MarkedEventReceiver for Locator<'a> {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        let is_key = matches!(self.frames.last(), Some((Frame::Mapping(None), _)));
+
+        if let Some(depth) = self.pending.take() {
+            if is_key {
+                self.found[depth] = Some(marker);
+            }
+        }
+
+        if let Event::Scalar(value, ..) = &event {
+            if is_key {
+                if self.found[2].is_none()
+                    && Some(value.as_str()) == self.key
+                    && self.is_at(&None, 1)
+                {
+                    self.found[2] = Some(marker);
+                }
+
+                if let Some((Frame::Mapping(key), _)) = self.frames.last_mut() {
+                    *key = Some(value.clone());
+                }
+
+                return;
+            }
+        }
+
+        let segment = match event {
+            Event::Scalar(..)
+            | Event::Alias(_)
+            | Event::SequenceStart(_)
+            | Event::MappingStart(_) => {
+                let segment = if is_key { None } else { self.next_segment() };
+
+                let depth = if self.found[0].is_none() && self.is_at(&segment, 0) {
+                    Some(0)
+                } else if self.found[1].is_none() && self.is_at(&segment, 1) {
+                    Some(1)
+                } else {
+                    None
+                };
+
+                if let Some(depth) = depth {
+                    self.found[depth] = Some(marker);
+
+                    if let Event::MappingStart(_) = event {
+                        self.pending = Some(depth);
+                    }
+                }
+
+                segment
+            },
+            _ => None,
+        };
+
+        match event {
+            Event::Scalar(..) | Event::Alias(_) => self.finish_node(),
+            Event::SequenceStart(_) => self.frames.push((Frame::Sequence(0), segment)),
+            Event::MappingStart(_) => self.frames.push((Frame::Mapping(None), segment)),
+            Event::SequenceEnd | Event::MappingEnd => {
+                self.frames.pop();
+                self.finish_node();
+            },
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::borrow::Cow;
+
+use super::{Captures, Regex};
+use crate::errors::RuleErrorKind;
+
+/// A modifier applied to the text of a capture group, such as `${1:upper}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Transform {
+    /// `upper`
+    Upper,
+    /// `lower`
+    Lower,
+    /// `title`: the first letter of every word is uppercased and the others are lowercased.
+    Title,
+    /// `dots`: underscores are replaced with dots.
+    Dots,
+    /// `strip_suffix=...`
+    StripSuffix(String),
+}
+
+impl Transform {
+    fn parse(s: &str) -> Option<Transform> {
+        let transform = match s {
+            "upper" => Transform::Upper,
+            "lower" => Transform::Lower,
+            "title" => Transform::Title,
+            "dots" => Transform::Dots,
+            _ => match s.strip_prefix("strip_suffix=") {
+                Some(suffix) if !suffix.is_empty() => Transform::StripSuffix(suffix.to_string()),
+                _ => return None,
+            },
+        };
+
+        Some(transform)
+    }
+
+    fn apply(&self, s: &str) -> String {
+        match self {
+            Transform::Upper => s.to_uppercase(),
+            Transform::Lower => s.to_lowercase(),
+            Transform::Title => {
+                let mut title = String::with_capacity(s.len());
+                let mut word_start = true;
+
+                for c in s.chars() {
+                    if word_start {
+                        title.extend(c.to_uppercase());
+                    } else {
+                        title.extend(c.to_lowercase());
+                    }
+
+                    word_start = !c.is_alphanumeric();
+                }
+
+                title
+            },
+            Transform::Dots => s.replace('_', "."),
+            Transform::StripSuffix(suffix) => {
+                s.strip_suffix(suffix.as_str()).unwrap_or(s).to_string()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Group(usize, Vec<Transform>),
+}
+
+/// A `*_replacement` template which is compiled when the rules are loaded.
+///
+/// `$0` is the whole match, `$1` to `$99` are the capture groups, `${name}` is a named capture group (`${12}` also works), and `$$` is a literal `$`. A `$` which does not start a reference is kept literally.
+///
+/// The braced form can have transforms separated by colons, such as `${1:lower:title}`, which are applied in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Template {
+    source: String,
+    parts:  Vec<Part>,
+}
+
+impl Template {
+    /// Create a template which has no reference, without parsing it.
+    #[inline]
+    pub(crate) fn literal<S: Into<String>>(s: S) -> Template {
+        let source = s.into();
+
+        Template {
+            parts: vec![Part::Literal(source.clone())],
+            source,
+        }
+    }
+
+    /// Compile a template whose named references are resolved against the regex.
+    pub(crate) fn parse(source: &str, regex: &Regex) -> Result<Template, RuleErrorKind> {
+        let bytes = source.as_bytes();
+
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut copied = 0;
+        let mut p = 0;
+
+        while p < bytes.len() {
+            if bytes[p] != b'$' {
+                p += 1;
+
+                continue;
+            }
+
+            let (group, transforms, end) = match bytes.get(p + 1) {
+                Some(b'$') => {
+                    literal.push_str(&source[copied..=p]);
+
+                    copied = p + 2;
+                    p += 2;
+
+                    continue;
+                },
+                Some(b'0'..=b'9') => {
+                    let mut end = p + 2;
+
+                    if end < bytes.len() && bytes[end].is_ascii_digit() {
+                        end += 1;
+                    }
+
+                    (source[(p + 1)..end].parse::<usize>().unwrap(), Vec::new(), end)
+                },
+                Some(b'{') => match source[(p + 2)..].find('}') {
+                    Some(length) => {
+                        let mut tokens = source[(p + 2)..(p + 2 + length)].split(':');
+
+                        let name = tokens.next().unwrap();
+
+                        let group = if !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit())
+                        {
+                            name.parse::<usize>().ok()
+                        } else {
+                            regex.capture_index(name)
+                        };
+
+                        let group = match group {
+                            Some(group) => group,
+                            None => {
+                                return Err(RuleErrorKind::UnknownCaptureName(name.to_string()))
+                            },
+                        };
+
+                        let transforms = tokens
+                            .map(|token| {
+                                Transform::parse(token).ok_or_else(|| {
+                                    RuleErrorKind::UnknownTransform(token.to_string())
+                                })
+                            })
+                            .collect::<Result<_, _>>()?;
+
+                        (group, transforms, p + 3 + length)
+                    },
+                    None => {
+                        p += 1;
+
+                        continue;
+                    },
+                },
+                _ => {
+                    p += 1;
+
+                    continue;
+                },
+            };
+
+            literal.push_str(&source[copied..p]);
+
+            if !literal.is_empty() {
+                parts.push(Part::Literal(std::mem::take(&mut literal)));
+            }
+
+            parts.push(Part::Group(group, transforms));
+
+            copied = end;
+            p = end;
+        }
+
+        literal.push_str(&source[copied..]);
+
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+
+        Ok(Template {
+            source: source.to_string(),
+            parts,
+        })
+    }
+
+    /// The raw template.
+    #[inline]
+    pub(crate) fn as_str(&self) -> &str {
+        self.source.as_str()
+    }
+
+    /// Whether this template references any capture group.
+    #[inline]
+    pub(crate) fn has_groups(&self) -> bool {
+        self.parts.iter().any(|part| matches!(part, Part::Group(..)))
+    }
+
+    /// The capture groups referenced by this template, in order.
+    #[inline]
+    pub(crate) fn groups(&self) -> impl Iterator<Item = usize> + '_ {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Group(group, _) => Some(*group),
+            Part::Literal(_) => None,
+        })
+    }
+
+    /// Substitute the references. A group which the regex does not have, or which did not participate in the match, is replaced with an empty string.
+    pub(crate) fn render<'a>(&'a self, captures: &Captures<'a>) -> Cow<'a, str> {
+        match self.parts.as_slice() {
+            [] => Cow::from(""),
+            [Part::Literal(s)] => Cow::from(s.as_str()),
+            parts => {
+                let mut s = String::with_capacity(self.source.len());
+
+                for part in parts {
+                    match part {
+                        Part::Literal(literal) => s.push_str(literal),
+                        Part::Group(group, transforms) => {
+                            if *group < captures.len() {
+                                let text = captures.at(*group).unwrap_or_default();
+
+                                match transforms.split_first() {
+                                    Some((first, rest)) => {
+                                        let text = rest
+                                            .iter()
+                                            .fold(first.apply(text), |text, transform| {
+                                                transform.apply(&text)
+                                            });
+
+                                        s.push_str(&text);
+                                    },
+                                    None => s.push_str(text),
+                                }
+                            }
+                        },
+                    }
+                }
+
+                Cow::from(s)
+            },
+        }
+    }
+}
@@ -1,11 +1,29 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, convert::Infallible};
 
 use rocket::{
+    http::Status,
     outcome::Outcome,
     request::{FromRequest, Outcome as OutcomeResult, Request},
 };
 
-use crate::{models::*, UserAgentParser};
+use crate::{models::*, MissingUserAgentParser, UserAgentParser};
+
+#[inline]
+fn user_agent_parser<'r>(
+    request: &'r Request<'_>,
+) -> Result<&'r UserAgentParser, MissingUserAgentParser> {
+    request.rocket().state::<UserAgentParser>().ok_or(MissingUserAgentParser)
+}
+
+#[inline]
+fn into_outcome<S>(
+    result: Result<S, MissingUserAgentParser>,
+) -> OutcomeResult<S, MissingUserAgentParser> {
+    match result {
+        Ok(value) => Outcome::Success(value),
+        Err(error) => Outcome::Error((Status::InternalServerError, error)),
+    }
+}
 
 fn from_request_user_agent<'r>(request: &'r Request<'_>) -> UserAgent<'r> {
     let user_agent: Option<Cow<'r, str>> =
@@ -18,7 +36,7 @@ fn from_request_user_agent<'r>(request: &'r Request<'_>) -> UserAgent<'r> {
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for UserAgent<'r> {
-    type Error = ();
+    type Error = Infallible;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
         Outcome::Success(from_request_user_agent(request))
@@ -27,7 +45,7 @@ impl<'r> FromRequest<'r> for UserAgent<'r> {
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for &UserAgent<'r> {
-    type Error = ();
+    type Error = Infallible;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
         let cache = request.local_cache(|| from_request_user_agent(request).into_owned());
@@ -36,162 +54,194 @@ impl<'r> FromRequest<'r> for &UserAgent<'r> {
     }
 }
 
-fn from_request_product<'r>(request: &'r Request<'_>) -> Product<'r> {
-    let user_agent_parser = request.rocket().state::<UserAgentParser>().unwrap();
+/// The result of parsing the user agent, cached in one `local_cache` slot of a request and shared by the guards of all models.
+struct ParsedUserAgentCache(Result<ParsedUserAgent<'static>, MissingUserAgentParser>);
 
-    let user_agent: Option<&str> = request.headers().get("user-agent").next();
+fn from_request_parsed_user_agent<'r>(
+    request: &'r Request<'_>,
+) -> Result<&'r ParsedUserAgent<'static>, MissingUserAgentParser> {
+    let cache = request.local_cache(|| {
+        ParsedUserAgentCache(user_agent_parser(request).map(|user_agent_parser| {
+            let user_agent: Option<&str> = request.headers().get("user-agent").next();
 
-    match user_agent {
-        Some(user_agent) => user_agent_parser.parse_product(user_agent),
-        None => Product::default(),
-    }
+            match user_agent {
+                Some(user_agent) => user_agent_parser.parse_all(user_agent).into_owned(),
+                None => ParsedUserAgent::default(),
+            }
+        }))
+    });
+
+    cache.0.as_ref().map_err(|error| *error)
 }
 
-#[rocket::async_trait]
-impl<'r> FromRequest<'r> for Product<'r> {
-    type Error = ();
+/// Borrow a field of a cached model instead of cloning its string.
+#[inline]
+fn borrow_field<'r>(field: &'r Option<Cow<'static, str>>) -> Option<Cow<'r, str>> {
+    field.as_deref().map(Cow::Borrowed)
+}
 
-    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        Outcome::Success(from_request_product(request))
+fn borrow_product<'r>(product: &'r Product<'static>) -> Product<'r> {
+    Product {
+        name:  borrow_field(&product.name),
+        major: borrow_field(&product.major),
+        minor: borrow_field(&product.minor),
+        patch: borrow_field(&product.patch),
     }
 }
 
-#[rocket::async_trait]
-impl<'r> FromRequest<'r> for &Product<'r> {
-    type Error = ();
-
-    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        let cache =
-            request.local_cache_async(async { from_request_product(request).into_owned() }).await;
+fn borrow_os<'r>(os: &'r OS<'static>) -> OS<'r> {
+    OS {
+        name:        borrow_field(&os.name),
+        major:       borrow_field(&os.major),
+        minor:       borrow_field(&os.minor),
+        patch:       borrow_field(&os.patch),
+        patch_minor: borrow_field(&os.patch_minor),
+    }
+}
 
-        Outcome::Success(cache)
+fn borrow_device<'r>(device: &'r Device<'static>) -> Device<'r> {
+    Device {
+        name:  borrow_field(&device.name),
+        brand: borrow_field(&device.brand),
+        model: borrow_field(&device.model),
     }
 }
 
-fn from_request_os<'r>(request: &'r Request<'_>) -> OS<'r> {
-    let user_agent_parser = request.rocket().state::<UserAgentParser>().unwrap();
+fn borrow_cpu<'r>(cpu: &'r CPU<'static>) -> CPU<'r> {
+    CPU {
+        architecture: borrow_field(&cpu.architecture)
+    }
+}
 
-    let user_agent: Option<&str> = request.headers().get("user-agent").next();
+fn borrow_engine<'r>(engine: &'r Engine<'static>) -> Engine<'r> {
+    Engine {
+        name:  borrow_field(&engine.name),
+        major: borrow_field(&engine.major),
+        minor: borrow_field(&engine.minor),
+        patch: borrow_field(&engine.patch),
+    }
+}
 
-    match user_agent {
-        Some(user_agent) => user_agent_parser.parse_os(user_agent),
-        None => OS::default(),
+fn borrow_parsed_user_agent<'r>(parsed: &'r ParsedUserAgent<'static>) -> ParsedUserAgent<'r> {
+    ParsedUserAgent {
+        product: borrow_product(&parsed.product),
+        os:      borrow_os(&parsed.os),
+        device:  borrow_device(&parsed.device),
+        cpu:     borrow_cpu(&parsed.cpu),
+        engine:  borrow_engine(&parsed.engine),
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for OS<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for ParsedUserAgent<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        Outcome::Success(from_request_os(request))
+        into_outcome(from_request_parsed_user_agent(request).map(borrow_parsed_user_agent))
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for &OS<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for &ParsedUserAgent<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        let cache =
-            request.local_cache_async(async { from_request_os(request).into_owned() }).await;
-
-        Outcome::Success(cache)
+        into_outcome(from_request_parsed_user_agent(request))
     }
 }
 
-fn from_request_device<'r>(request: &'r Request<'_>) -> Device<'r> {
-    let user_agent_parser = request.rocket().state::<UserAgentParser>().unwrap();
-
-    let user_agent: Option<&str> = request.headers().get("user-agent").next();
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Product<'r> {
+    type Error = MissingUserAgentParser;
 
-    match user_agent {
-        Some(user_agent) => user_agent_parser.parse_device(user_agent),
-        None => Device::default(),
+    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
+        into_outcome(
+            from_request_parsed_user_agent(request).map(|parsed| borrow_product(&parsed.product)),
+        )
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for Device<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for &Product<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        Outcome::Success(from_request_device(request))
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| &parsed.product))
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for &Device<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for OS<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        let cache =
-            request.local_cache_async(async { from_request_device(request).into_owned() }).await;
-
-        Outcome::Success(cache)
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| borrow_os(&parsed.os)))
     }
 }
 
-fn from_request_cpu<'r>(request: &'r Request<'_>) -> CPU<'r> {
-    let user_agent_parser = request.rocket().state::<UserAgentParser>().unwrap();
-
-    let user_agent: Option<&str> = request.headers().get("user-agent").next();
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &OS<'r> {
+    type Error = MissingUserAgentParser;
 
-    match user_agent {
-        Some(user_agent) => user_agent_parser.parse_cpu(user_agent),
-        None => CPU::default(),
+    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| &parsed.os))
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for CPU<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for Device<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        Outcome::Success(from_request_cpu(request))
+        into_outcome(
+            from_request_parsed_user_agent(request).map(|parsed| borrow_device(&parsed.device)),
+        )
     }
 }
 
 #[rocket::async_trait]
-impl<'r> FromRequest<'r> for &CPU<'r> {
-    type Error = ();
+impl<'r> FromRequest<'r> for &Device<'r> {
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        let cache =
-            request.local_cache_async(async { from_request_cpu(request).into_owned() }).await;
-
-        Outcome::Success(cache)
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| &parsed.device))
     }
 }
 
-fn from_request_engine<'r>(request: &'r Request<'_>) -> Engine<'r> {
-    let user_agent_parser = request.rocket().state::<UserAgentParser>().unwrap();
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CPU<'r> {
+    type Error = MissingUserAgentParser;
+
+    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| borrow_cpu(&parsed.cpu)))
+    }
+}
 
-    let user_agent: Option<&str> = request.headers().get("user-agent").next();
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &CPU<'r> {
+    type Error = MissingUserAgentParser;
 
-    match user_agent {
-        Some(user_agent) => user_agent_parser.parse_engine(user_agent),
-        None => Engine::default(),
+    async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| &parsed.cpu))
     }
 }
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for Engine<'r> {
-    type Error = ();
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        Outcome::Success(from_request_engine(request))
+        into_outcome(
+            from_request_parsed_user_agent(request).map(|parsed| borrow_engine(&parsed.engine)),
+        )
     }
 }
 
 #[rocket::async_trait]
 impl<'r> FromRequest<'r> for &Engine<'r> {
-    type Error = ();
+    type Error = MissingUserAgentParser;
 
     async fn from_request(request: &'r Request<'_>) -> OutcomeResult<Self, Self::Error> {
-        let cache =
-            request.local_cache_async(async { from_request_engine(request).into_owned() }).await;
-
-        Outcome::Success(cache)
+        into_outcome(from_request_parsed_user_agent(request).map(|parsed| &parsed.engine))
     }
 }
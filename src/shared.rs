@@ -0,0 +1,110 @@
+use std::{path::Path, sync::Arc};
+
+use arc_swap::ArcSwap;
+
+use crate::{UserAgentParser, UserAgentParserError};
+
+/// A handle to a `UserAgentParser` instance which can be replaced while it is in use. Cloning the handle is cheap, and all clones share the same parser.
+///
+/// Readers get a snapshot without locking, so a reload never blocks the parsing, and a snapshot keeps working with the old rules until it is dropped.
+#[derive(Debug, Clone)]
+pub struct SharedUserAgentParser {
+    inner: Arc<ArcSwap<UserAgentParser>>,
+}
+
+impl SharedUserAgentParser {
+    #[inline]
+    pub fn new(user_agent_parser: UserAgentParser) -> SharedUserAgentParser {
+        SharedUserAgentParser {
+            inner: Arc::new(ArcSwap::from_pointee(user_agent_parser))
+        }
+    }
+
+    /// Get a snapshot of the current parser.
+    #[inline]
+    pub fn load(&self) -> Arc<UserAgentParser> {
+        self.inner.load_full()
+    }
+
+    /// Replace the current parser atomically.
+    #[inline]
+    pub fn store(&self, user_agent_parser: UserAgentParser) {
+        self.inner.store(Arc::new(user_agent_parser));
+    }
+
+    /// Read the list of regular expressions (YAML data) from a file and replace the current parser with it. If the file fails to load, the current parser is kept.
+    #[inline]
+    pub fn reload_from_path<P: AsRef<Path>>(&self, path: P) -> Result<(), UserAgentParserError> {
+        self.store(UserAgentParser::from_path(path)?);
+
+        Ok(())
+    }
+
+    /// Watch a file and reload the parser from it whenever it is created or modified, until the returned `UserAgentParserWatcher` instance is dropped.
+    ///
+    /// The directory of the file is watched, so that replacing the file (as editors and deployment tools do) is also noticed. If the file fails to load, for example because it is being written, the current parser is kept and the error is passed to `on_error`.
+    #[cfg(feature = "watch")]
+    pub fn watch<P: AsRef<Path>, F: Fn(UserAgentParserError) + Send + 'static>(
+        &self,
+        path: P,
+        on_error: F,
+    ) -> Result<UserAgentParserWatcher, notify::Error> {
+        use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+        let path = path.as_ref().to_path_buf();
+
+        let file_name = match path.file_name() {
+            Some(file_name) => file_name.to_os_string(),
+            None => {
+                return Err(notify::Error::generic("the path is not a file").add_path(path));
+            },
+        };
+
+        let directory = match path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory.to_path_buf(),
+            _ => Path::new(".").to_path_buf(),
+        };
+
+        let shared = self.clone();
+        let watched_path = path.clone();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                return;
+            }
+
+            if !event.paths.iter().any(|path| path.file_name() == Some(file_name.as_os_str())) {
+                return;
+            }
+
+            if let Err(err) = shared.reload_from_path(&watched_path) {
+                on_error(err);
+            }
+        })?;
+
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+        Ok(UserAgentParserWatcher {
+            _watcher: watcher
+        })
+    }
+}
+
+impl From<UserAgentParser> for SharedUserAgentParser {
+    #[inline]
+    fn from(user_agent_parser: UserAgentParser) -> Self {
+        SharedUserAgentParser::new(user_agent_parser)
+    }
+}
+
+/// The watcher created by the `SharedUserAgentParser::watch` method. The file stops being watched when it is dropped.
+#[cfg(feature = "watch")]
+#[derive(Debug)]
+pub struct UserAgentParserWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
@@ -0,0 +1,121 @@
+//! A minimal parser of the structured field values (RFC 8941) used by User-Agent Client Hints.
+
+/// Parse an sf-list whose members are sf-strings (or tokens) with parameters, such as `"Chromium";v="110", "Not A(Brand";v="24"`. Returns `(member, the value of the "v" parameter)` pairs. Members which cannot be parsed are skipped.
+pub(crate) fn parse_brand_list(value: &str) -> Vec<(String, Option<String>)> {
+    let mut brands = Vec::new();
+
+    let mut rest = value;
+
+    loop {
+        rest = rest.trim_start();
+
+        if rest.is_empty() {
+            break;
+        }
+
+        let (brand, after) = match parse_bare_item(rest) {
+            Some(item) => item,
+            None => match rest.find(',') {
+                Some(index) => {
+                    rest = &rest[index + 1..];
+
+                    continue;
+                },
+                None => break,
+            },
+        };
+
+        rest = after;
+
+        let mut version = None;
+
+        // parameters
+        while let Some(after) = rest.trim_start().strip_prefix(';') {
+            let after = after.trim_start();
+
+            let key_len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '*')))
+                .unwrap_or(after.len());
+
+            let key = &after[..key_len];
+
+            rest = &after[key_len..];
+
+            if let Some(after) = rest.strip_prefix('=') {
+                match parse_bare_item(after) {
+                    Some((value, after)) => {
+                        if key == "v" {
+                            version = Some(value);
+                        }
+
+                        rest = after;
+                    },
+                    None => break,
+                }
+            }
+        }
+
+        brands.push((brand, version));
+
+        match rest.find(',') {
+            Some(index) => rest = &rest[index + 1..],
+            None => break,
+        }
+    }
+
+    brands
+}
+
+/// Parse an sf-string such as `"Windows"`. A bare token is also accepted.
+pub(crate) fn parse_string(value: &str) -> Option<String> {
+    let (s, rest) = parse_bare_item(value.trim())?;
+
+    if rest.trim().is_empty() {
+        Some(s)
+    } else {
+        None
+    }
+}
+
+/// Parse an sf-boolean, `?1` or `?0`.
+pub(crate) fn parse_boolean(value: &str) -> Option<bool> {
+    match value.trim() {
+        "?1" => Some(true),
+        "?0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parse an sf-string, a token, or a number at the beginning of the value. Returns the item and the rest.
+fn parse_bare_item(value: &str) -> Option<(String, &str)> {
+    let value = value.trim_start();
+
+    if let Some(value) = value.strip_prefix('"') {
+        let mut s = String::new();
+
+        let mut chars = value.char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Some((s, &value[i + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, c @ ('"' | '\\'))) => s.push(c),
+                    _ => return None,
+                },
+                _ => s.push(c),
+            }
+        }
+
+        None
+    } else {
+        let len = value
+            .find(|c: char| matches!(c, ',' | ';' | '=') || c.is_ascii_whitespace())
+            .unwrap_or(value.len());
+
+        if len == 0 {
+            None
+        } else {
+            Some((value[..len].to_string(), &value[len..]))
+        }
+    }
+}
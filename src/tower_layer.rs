@@ -0,0 +1,165 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::{header::USER_AGENT, Request};
+use tower_layer::Layer;
+use tower_service::Service;
+
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+use crate::ParsedSections;
+use crate::{models::*, Sections, UserAgentParser};
+
+/// A tower `Layer` which parses the `User-Agent` header of each request and inserts the owned `ParsedUserAgent<'static>` into the request extensions.
+///
+/// All sections are parsed by default. Sections which are not parsed are left default, and the axum and actix-web extractors behind this layer parse them on demand. Client Hints headers are not read unless the `client_hints` option is enabled.
+#[derive(Debug, Clone)]
+pub struct UserAgentLayer {
+    user_agent_parser: Arc<UserAgentParser>,
+    sections:          Sections,
+    client_hints:      bool,
+}
+
+impl UserAgentLayer {
+    /// Create a `UserAgentLayer` instance which parses all sections.
+    #[inline]
+    pub fn new(user_agent_parser: Arc<UserAgentParser>) -> UserAgentLayer {
+        UserAgentLayer {
+            user_agent_parser,
+            sections: Sections::ALL,
+            client_hints: false,
+        }
+    }
+
+    /// Whether to parse the product information. The default value is `true`.
+    #[inline]
+    pub fn product(mut self, enable: bool) -> Self {
+        self.sections.product = enable;
+
+        self
+    }
+
+    /// Whether to parse the OS information. The default value is `true`.
+    #[inline]
+    pub fn os(mut self, enable: bool) -> Self {
+        self.sections.os = enable;
+
+        self
+    }
+
+    /// Whether to parse the device information. The default value is `true`.
+    #[inline]
+    pub fn device(mut self, enable: bool) -> Self {
+        self.sections.device = enable;
+
+        self
+    }
+
+    /// Whether to parse the CPU information. The default value is `true`.
+    #[inline]
+    pub fn cpu(mut self, enable: bool) -> Self {
+        self.sections.cpu = enable;
+
+        self
+    }
+
+    /// Whether to parse the engine information. The default value is `true`.
+    #[inline]
+    pub fn engine(mut self, enable: bool) -> Self {
+        self.sections.engine = enable;
+
+        self
+    }
+
+    /// Whether to read the `Sec-CH-UA-*` headers and merge them over the parsed sections. The default value is `false`.
+    #[inline]
+    pub fn client_hints(mut self, enable: bool) -> Self {
+        self.client_hints = enable;
+
+        self
+    }
+
+    fn parse<B>(&self, req: &Request<B>) -> ParsedUserAgent<'static> {
+        let headers = req.headers();
+
+        let mut parsed_user_agent =
+            match headers.get(USER_AGENT).and_then(|value| value.to_str().ok()) {
+                Some(user_agent) => {
+                    self.user_agent_parser.parse_sections(user_agent, self.sections).into_owned()
+                },
+                None => ParsedUserAgent::default(),
+            };
+
+        if self.client_hints {
+            let client_hints =
+                ClientHints::from_headers(ClientHints::HEADER_NAMES.iter().filter_map(|&name| {
+                    headers.get(name).and_then(|value| value.to_str().ok()).map(|v| (name, v))
+                }));
+
+            if !client_hints.is_empty() {
+                if self.sections.product {
+                    client_hints.merge_product(&mut parsed_user_agent.product);
+                }
+
+                if self.sections.os {
+                    client_hints.merge_os(&mut parsed_user_agent.os);
+                }
+
+                if self.sections.device {
+                    client_hints.merge_device(&mut parsed_user_agent.device);
+                }
+
+                if self.sections.cpu {
+                    client_hints.merge_cpu(&mut parsed_user_agent.cpu);
+                }
+            }
+        }
+
+        parsed_user_agent
+    }
+}
+
+impl<S> Layer<S> for UserAgentLayer {
+    type Service = UserAgentService<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        UserAgentService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service created by `UserAgentLayer`.
+#[derive(Debug, Clone)]
+pub struct UserAgentService<S> {
+    inner: S,
+    layer: UserAgentLayer,
+}
+
+impl<S, B> Service<Request<B>> for UserAgentService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Error = S::Error;
+    type Future = S::Future;
+    type Response = S::Response;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let parsed_user_agent = self.layer.parse(&req);
+
+        req.extensions_mut().insert(parsed_user_agent);
+        #[cfg(any(feature = "axum", feature = "actix-web"))]
+        req.extensions_mut().insert(ParsedSections(self.layer.sections));
+
+        self.inner.call(req)
+    }
+}
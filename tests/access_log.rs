@@ -0,0 +1,185 @@
+#![cfg(feature = "access-log")]
+
+use std::borrow::Cow;
+
+use user_agent_parser::{
+    AccessLogStats, Dimension, LogFormat, LogLine, UserAgentExtractor, UserAgentParser,
+};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+  - regex: '(Chrome)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Windows) NT (\d+)\.(\d+)'
+  - regex: '(Ubuntu)'
+"#;
+
+const CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like \
+                      Gecko) Chrome/110.0.0.0 Safari/537.36";
+const FIREFOX: &str =
+    "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/109.0";
+
+#[test]
+fn test_combined() {
+    let mut extractor = UserAgentExtractor::new(LogFormat::Combined);
+
+    assert_eq!(
+        LogLine::Request(Some(Cow::from(CHROME))),
+        extractor.extract(&format!(
+            "127.0.0.1 - - [10/Oct/2026:13:55:36 +0000] \"GET / HTTP/1.1\" 200 612 \"-\" \
+             \"{CHROME}\"\n"
+        ))
+    );
+
+    // escaped quotes and extra fields after the user agent
+    assert_eq!(
+        LogLine::Request(Some(Cow::from("Say \"hi\""))),
+        extractor.extract(
+            "::1 - - [10/Oct/2026:13:55:36 +0000] \"GET /\\\"a HTTP/1.1\" 200 612 \"https://example.com/\" \"Say \\x22hi\\\"\" \"10.0.0.1\""
+        )
+    );
+
+    assert_eq!(
+        LogLine::Request(None),
+        extractor
+            .extract("::1 - - [10/Oct/2026:13:55:36 +0000] \"GET / HTTP/1.1\" 200 612 \"-\" \"-\"")
+    );
+
+    assert_eq!(
+        LogLine::Skipped,
+        extractor.extract("::1 - - [10/Oct/2026:13:55:36 +0000] \"GET / HTTP/1.1\"")
+    );
+    assert_eq!(LogLine::Skipped, extractor.extract(""));
+}
+
+#[test]
+fn test_w3c() {
+    let mut extractor = UserAgentExtractor::new(LogFormat::W3C);
+
+    assert_eq!(
+        LogLine::Skipped,
+        extractor.extract("#Software: Microsoft Internet Information Services 10.0")
+    );
+
+    assert_eq!(
+        LogLine::Skipped,
+        extractor.extract("2026-10-10 13:55:36 GET / - 80 Mozilla/5.0 200")
+    );
+
+    assert_eq!(
+        LogLine::Skipped,
+        extractor.extract(
+            "#Fields: date time cs-method cs-uri-stem cs-uri-query s-port cs(User-Agent) sc-status"
+        )
+    );
+
+    assert_eq!(
+        LogLine::Request(Some(Cow::from(CHROME))),
+        extractor.extract(&format!(
+            "2026-10-10 13:55:36 GET / - 443 {} 200\r\n",
+            CHROME.replace(' ', "+")
+        ))
+    );
+
+    assert_eq!(LogLine::Request(None), extractor.extract("2026-10-10 13:55:36 GET / - 443 - 200"));
+}
+
+#[test]
+fn test_json_lines() {
+    let mut extractor = UserAgentExtractor::new(LogFormat::JsonLines(String::from("ua")));
+
+    assert_eq!(
+        LogLine::Request(Some(Cow::from(FIREFOX))),
+        extractor.extract(&format!(r#"{{"status":200,"ua":"{FIREFOX}"}}"#))
+    );
+
+    assert_eq!(LogLine::Request(None), extractor.extract(r#"{"status":200}"#));
+    assert_eq!(LogLine::Skipped, extractor.extract("not json"));
+}
+
+#[test]
+fn test_report() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let mut log = String::new();
+
+    for (user_agent, count) in [(CHROME, 5), (FIREFOX, 3), ("curl/8.0", 1), ("-", 1)] {
+        for _ in 0..count {
+            log.push_str(&format!(
+                "127.0.0.1 - - [10/Oct/2026:13:55:36 +0000] \"GET / HTTP/1.1\" 200 612 \"-\" \
+                 \"{user_agent}\"\n"
+            ));
+        }
+    }
+
+    log.push_str("garbage\n");
+
+    let mut stats = AccessLogStats::new();
+
+    stats.read(&ua_parser, LogFormat::Combined, log.as_bytes()).unwrap();
+
+    assert_eq!(10, stats.requests());
+    assert_eq!(1, stats.without_user_agent());
+    assert_eq!(1, stats.skipped());
+
+    let report = stats.report(Dimension::Product, true, None);
+
+    assert_eq!(9, report.total);
+    assert_eq!(
+        vec![("Chrome", Some("110"), 5), ("Firefox", Some("109"), 3), ("Other", None, 1)],
+        report
+            .entries
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.major.as_deref(), entry.count))
+            .collect::<Vec<_>>()
+    );
+
+    let report = stats.report(Dimension::OS, false, Some(1));
+
+    assert_eq!(
+        vec![("Windows", 5), ("Other", 4)],
+        report.entries.iter().map(|entry| (entry.name.as_str(), entry.count)).collect::<Vec<_>>()
+    );
+    assert!((report.entries[1].share - 400.0 / 9.0).abs() < 1e-9);
+
+    assert_eq!(
+        "OS       Requests    Share\nWindows         5   55.56%\nOther           4   44.44%\n",
+        report.to_string()
+    );
+}
+
+#[test]
+fn test_report_top_with_other_family() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let mut log = String::new();
+
+    for (user_agent, count) in [(CHROME, 2), (FIREFOX, 1), ("curl/8.0", 4)] {
+        for _ in 0..count {
+            log.push_str(&format!(
+                "127.0.0.1 - - [10/Oct/2026:13:55:36 +0000] \"GET / HTTP/1.1\" 200 612 \"-\" \
+                 \"{user_agent}\"\n"
+            ));
+        }
+    }
+
+    let mut stats = AccessLogStats::new();
+
+    stats.read(&ua_parser, LogFormat::Combined, log.as_bytes()).unwrap();
+
+    let entries = |top| {
+        stats
+            .report(Dimension::Product, false, Some(top))
+            .entries
+            .into_iter()
+            .map(|entry| (entry.name, entry.count))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(vec![(String::from("Chrome"), 2), (String::from("Other"), 5)], entries(1));
+    assert_eq!(
+        vec![(String::from("Chrome"), 2), (String::from("Firefox"), 1), (String::from("Other"), 4)],
+        entries(2)
+    );
+}
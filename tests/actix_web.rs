@@ -0,0 +1,61 @@
+#![cfg(feature = "actix-web")]
+
+use actix_web::{
+    error::ErrorServiceUnavailable, http::StatusCode, test::TestRequest, web, FromRequest,
+    HttpMessage,
+};
+use user_agent_parser::{
+    ParsedUserAgent, Product, UserAgent, UserAgentParser, UserAgentParserConfig, OS,
+};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Ubuntu)/(\d+)\.(\d+)'
+device_parsers:
+  - regex: '(X11)'
+"#;
+
+const USER_AGENT: &str = "Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 \
+                          Ubuntu/10.04 (lucid) Firefox/3.6.12";
+
+#[actix_web::test]
+async fn test_app_data() {
+    let req = TestRequest::default()
+        .insert_header(("user-agent", USER_AGENT))
+        .app_data(web::Data::new(UserAgentParser::from_str(REGEXES).unwrap()))
+        .to_http_request();
+
+    let user_agent = UserAgent::extract(&req).await.unwrap();
+    let product = Product::extract(&req).await.unwrap();
+    let os = OS::extract(&req).await.unwrap();
+
+    assert_eq!(Some(USER_AGENT), user_agent.user_agent.as_deref());
+    assert_eq!(Some("Firefox"), product.name.as_deref());
+    assert_eq!(Some("Ubuntu"), os.name.as_deref());
+
+    // the result is cached in the extensions
+    assert!(req.extensions().get::<ParsedUserAgent<'static>>().is_some());
+}
+
+#[actix_web::test]
+async fn test_missing_parser() {
+    let req = TestRequest::default().insert_header(("user-agent", USER_AGENT)).to_http_request();
+
+    let error = Product::extract(&req).await.unwrap_err();
+
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, error.as_response_error().status_code());
+
+    let req = TestRequest::default()
+        .insert_header(("user-agent", USER_AGENT))
+        .app_data(
+            UserAgentParserConfig::default()
+                .error_handler(|error, _| ErrorServiceUnavailable(error)),
+        )
+        .to_http_request();
+
+    let error = ParsedUserAgent::extract(&req).await.unwrap_err();
+
+    assert_eq!(StatusCode::SERVICE_UNAVAILABLE, error.as_response_error().status_code());
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "axum")]
+
+use std::sync::Arc;
+
+use axum::{extract::FromRequestParts, http::Request};
+use user_agent_parser::{
+    MissingUserAgentParser, ParsedUserAgent, Product, UserAgent, UserAgentParser, OS,
+};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Ubuntu)/(\d+)\.(\d+)'
+device_parsers:
+  - regex: '(X11)'
+"#;
+
+const USER_AGENT: &str = "Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 \
+                          Ubuntu/10.04 (lucid) Firefox/3.6.12";
+
+#[tokio::test]
+async fn test_state() {
+    let state = Arc::new(UserAgentParser::from_str(REGEXES).unwrap());
+
+    let (mut parts, _) =
+        Request::builder().header("user-agent", USER_AGENT).body(()).unwrap().into_parts();
+
+    let user_agent = UserAgent::from_request_parts(&mut parts, &state).await.unwrap();
+    let product = Product::from_request_parts(&mut parts, &state).await.unwrap();
+    let os = OS::from_request_parts(&mut parts, &state).await.unwrap();
+
+    assert_eq!(Some(USER_AGENT), user_agent.user_agent.as_deref());
+    assert_eq!(Some("Firefox"), product.name.as_deref());
+    assert_eq!(Some("Ubuntu"), os.name.as_deref());
+
+    // the result is cached in the extensions
+    assert!(parts.extensions.get::<ParsedUserAgent<'static>>().is_some());
+}
+
+#[tokio::test]
+async fn test_extension() {
+    let (mut parts, _) =
+        Request::builder().header("user-agent", USER_AGENT).body(()).unwrap().into_parts();
+
+    assert_eq!(
+        MissingUserAgentParser,
+        Product::from_request_parts(&mut parts, &()).await.unwrap_err()
+    );
+
+    parts.extensions.insert(Arc::new(UserAgentParser::from_str(REGEXES).unwrap()));
+
+    let parsed = ParsedUserAgent::from_request_parts(&mut parts, &()).await.unwrap();
+
+    assert_eq!(Some("Firefox"), parsed.product.name.as_deref());
+    assert_eq!(Some("X11"), parsed.device.name.as_deref());
+}
+
+#[tokio::test]
+async fn test_missing_user_agent() {
+    let state = Arc::new(UserAgentParser::from_str(REGEXES).unwrap());
+
+    let (mut parts, _) = Request::builder().body(()).unwrap().into_parts();
+
+    let user_agent = UserAgent::from_request_parts(&mut parts, &state).await.unwrap();
+    let product = Product::from_request_parts(&mut parts, &state).await.unwrap();
+
+    assert_eq!(None, user_agent.user_agent);
+    assert_eq!(None, product.name);
+}
@@ -0,0 +1,132 @@
+use user_agent_parser::{
+    BotCategory, ParseBotCategoryError, RuleErrorKind, RuleSection, UserAgentParser,
+    UserAgentParserError,
+};
+
+const EMPTY_RULES: &str = "user_agent_parsers:\n  - regex: '(Firefox)/(\\d+)'\n";
+
+#[test]
+fn test_built_in_rules() {
+    let ua_parser = UserAgentParser::from_str(EMPTY_RULES).unwrap();
+
+    let bot = ua_parser
+        .classify_bot("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)")
+        .unwrap();
+
+    assert_eq!(BotCategory::SearchCrawler, bot.category);
+    assert_eq!(Some("Googlebot"), bot.name.as_deref());
+    assert_eq!(Some("http://www.google.com/bot.html"), bot.url.as_deref());
+
+    let bot = ua_parser
+        .classify_bot(
+            "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; GPTBot/1.2; \
+             +https://openai.com/gptbot)",
+        )
+        .unwrap();
+
+    assert_eq!(BotCategory::AICrawler, bot.category);
+    assert_eq!(Some("GPTBot"), bot.name.as_deref());
+    assert_eq!(Some("https://openai.com/gptbot"), bot.url.as_deref());
+
+    let cases = [
+        (
+            "Mozilla/5.0 (compatible; AhrefsBot/7.0; +http://ahrefs.com/robot/)",
+            BotCategory::SEOTool,
+            "AhrefsBot",
+        ),
+        (
+            "Mozilla/5.0+(compatible; UptimeRobot/2.0; http://www.uptimerobot.com/)",
+            BotCategory::Monitoring,
+            "UptimeRobot",
+        ),
+        (
+            "Feedly/1.0 (+http://www.feedly.com/fetcher.html; 16 subscribers)",
+            BotCategory::FeedFetcher,
+            "Feedly",
+        ),
+        ("curl/8.4.0", BotCategory::HTTPLibrary, "curl"),
+        ("python-requests/2.31.0", BotCategory::HTTPLibrary, "python-requests"),
+        (
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) \
+             HeadlessChrome/120.0.0.0 Safari/537.36",
+            BotCategory::HeadlessBrowser,
+            "HeadlessChrome",
+        ),
+        ("Mozilla/5.0 (compatible; ExampleCrawler/1.0)", BotCategory::Other, "ExampleCrawler"),
+    ];
+
+    for (user_agent, category, name) in cases {
+        let bot = ua_parser.classify_bot(user_agent).unwrap();
+
+        assert_eq!(category, bot.category, "{user_agent}");
+        assert_eq!(Some(name), bot.name.as_deref(), "{user_agent}");
+    }
+
+    let browser = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like \
+                   Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+    assert!(ua_parser.classify_bot(browser).is_none());
+    assert!(!ua_parser.is_bot(browser));
+    assert!(ua_parser.is_bot("Wget/1.21.4"));
+}
+
+#[test]
+fn test_yaml_rules() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+bot_parsers:
+  - regex: '(InternalProbe)/(\d+)'
+    bot_category: 'monitoring'
+    name_replacement: 'Internal Probe $2'
+"#,
+    )
+    .unwrap();
+
+    let bot = ua_parser.classify_bot("InternalProbe/3 (+https://example.com/probe)").unwrap();
+
+    assert_eq!(BotCategory::Monitoring, bot.category);
+    assert_eq!(Some("Internal Probe 3"), bot.name.as_deref());
+    assert_eq!(Some("https://example.com/probe"), bot.url.as_deref());
+
+    // the built-in rules are still tried after the custom ones
+    assert!(ua_parser.is_bot("curl/8.4.0"));
+
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+bot_parsers:
+  built_in: false
+  rules:
+    - regex: '^curl/'
+      bot_category: 'other'
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(BotCategory::Other, ua_parser.classify_bot("curl/8.4.0").unwrap().category);
+    assert!(!ua_parser.is_bot("Wget/1.21.4"));
+}
+
+#[test]
+fn test_unknown_category() {
+    let err = match UserAgentParser::from_str(
+        r#"
+bot_parsers:
+  - regex: '(InternalProbe)'
+    bot_category: 'probe'
+"#,
+    ) {
+        Err(UserAgentParserError::InvalidRule(err)) => err,
+        _ => panic!("the YAML data should be invalid"),
+    };
+
+    assert_eq!(RuleSection::Bot, err.section);
+    assert_eq!(Some("bot_category"), err.key);
+    assert_eq!(Some((4, 5)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::InvalidValue(ref category) if category == "probe"));
+}
+
+#[test]
+fn test_parse_bot_category() {
+    assert_eq!(Ok(BotCategory::AICrawler), "AI_Crawler".parse());
+    assert_eq!(Err(ParseBotCategoryError), "scraper".parse::<BotCategory>());
+}
@@ -0,0 +1,132 @@
+use user_agent_parser::{RuleErrorKind, RuleSection, UserAgentParser, UserAgentParserError};
+
+const UPSTREAM: &str = r#"
+user_agent_parsers:
+  - regex: '(InternalApp)/(\d+)'
+    family_replacement: 'Upstream $1'
+  - regex: '(Chrome)/(\d+)'
+  - regex: '(Firefox)/(\d+)'
+os_parsers:
+  - regex: '(Windows) NT (\d+)'
+"#;
+
+const COMPANY: &str = r#"
+user_agent_parsers:
+  - regex: '(InternalApp)/(\d+)'
+    family_replacement: 'Company $1'
+engine_parsers:
+  - regex: '(InternalEngine)/(\d+)'
+"#;
+
+#[test]
+fn test_prepend_and_append() {
+    let ua_parser =
+        UserAgentParser::builder().append_str(UPSTREAM).prepend_str(COMPANY).build().unwrap();
+
+    let product = ua_parser.parse_product("InternalApp/3");
+
+    assert_eq!(Some("Company InternalApp"), product.name.as_deref());
+
+    let ua_parser =
+        UserAgentParser::builder().append_str(UPSTREAM).append_str(COMPANY).build().unwrap();
+
+    let product = ua_parser.parse_product("InternalApp/3");
+
+    assert_eq!(Some("Upstream InternalApp"), product.name.as_deref());
+}
+
+#[test]
+fn test_disable_rules() {
+    let ua_parser = UserAgentParser::builder()
+        .append_str(UPSTREAM)
+        .disable_rule(RuleSection::Product, 1)
+        .disable_regex(RuleSection::OS, r"(Windows) NT (\d+)")
+        .append_str(COMPANY)
+        .build()
+        .unwrap();
+
+    assert_eq!(Some("Other"), ua_parser.parse_product("Chrome/110").name.as_deref());
+    assert_eq!(Some("Firefox"), ua_parser.parse_product("Firefox/110").name.as_deref());
+    assert_eq!(Some("Other"), ua_parser.parse_os("Windows NT 10").name.as_deref());
+}
+
+#[test]
+fn test_disable_rule_before_source() {
+    let ua_parser = UserAgentParser::builder()
+        .disable_rule(RuleSection::Product, 1)
+        .append_str(UPSTREAM)
+        .build()
+        .unwrap();
+
+    assert_eq!(Some("Other"), ua_parser.parse_product("Chrome/110").name.as_deref());
+    assert_eq!(Some("Firefox"), ua_parser.parse_product("Firefox/110").name.as_deref());
+}
+
+#[test]
+fn test_unknown_disabled_rule() {
+    let builders = [
+        UserAgentParser::builder().append_str(UPSTREAM).disable_rule(RuleSection::Product, 3),
+        UserAgentParser::builder().append_str(UPSTREAM).disable_rule(RuleSection::Device, 0),
+        UserAgentParser::builder().disable_rule(RuleSection::Product, 0),
+    ];
+
+    for builder in builders {
+        let err = match builder.build() {
+            Err(UserAgentParserError::InvalidRule(err)) => err,
+            _ => panic!("the disabled rule should not exist"),
+        };
+
+        assert!(err.index.is_some());
+        assert!(matches!(err.kind, RuleErrorKind::UnknownRule));
+    }
+}
+
+#[test]
+fn test_unmatched_disabled_regex() {
+    let err = match UserAgentParser::builder()
+        .append_str(UPSTREAM)
+        .disable_regex(RuleSection::OS, r"(Linux)")
+        .build()
+    {
+        Err(UserAgentParserError::InvalidRule(err)) => err,
+        _ => panic!("the disabled regex should not match any rule"),
+    };
+
+    assert_eq!(RuleSection::OS, err.section);
+    assert_eq!(None, err.index);
+    assert_eq!(Some("(Linux)"), err.regex.as_deref());
+    assert!(matches!(err.kind, RuleErrorKind::UnknownRule));
+    assert_eq!("os_parsers: there is no such rule to disable (regex: `(Linux)`)", err.to_string());
+}
+
+#[test]
+fn test_merge_parsers() {
+    const USER_AGENT: &str =
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:109.0) Gecko/20100101 Firefox/110.0";
+
+    let single = UserAgentParser::from_str(COMPANY).unwrap();
+
+    let index = single.explain_engine(USER_AGENT).matched.unwrap().index;
+
+    // the built-in engine rules are added only once, after the rules of both sources
+    let merged = UserAgentParser::builder()
+        .append_parser(UserAgentParser::from_str(COMPANY).unwrap())
+        .append_parser(single)
+        .build()
+        .unwrap();
+
+    let explanation = merged.explain_engine(USER_AGENT);
+
+    assert_eq!(Some("Gecko"), explanation.result.name.as_deref());
+    assert_eq!(index + 1, explanation.matched.unwrap().index);
+}
+
+#[test]
+fn test_missing_path() {
+    let result = UserAgentParser::builder()
+        .append_str(UPSTREAM)
+        .prepend_path("/nonexistent/regexes.yaml")
+        .build();
+
+    assert!(matches!(result, Err(UserAgentParserError::IOError(_))));
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Ubuntu)/(\d+)\.(\d+)'
+"#;
+
+const FIREFOX: &str =
+    "Mozilla/5.0 (X11; Ubuntu/22.04; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/110.0";
+
+fn regexes_path(name: &str) -> PathBuf {
+    let path =
+        env::temp_dir().join(format!("user-agent-parser-cli-{name}-{}.yaml", std::process::id()));
+
+    fs::write(&path, REGEXES).unwrap();
+
+    path
+}
+
+fn command() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_user-agent-parser"))
+}
+
+#[test]
+fn test_jsonl() {
+    let path = regexes_path("jsonl");
+
+    let output = command()
+        .arg("--regexes")
+        .arg(&path)
+        .args([
+            "--columns",
+            "product.name,product.major,os.name,cpu.architecture",
+            FIREFOX,
+            "curl/8.0",
+        ])
+        .output()
+        .unwrap();
+
+    fs::remove_file(path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        concat!(
+            r#"{"product":{"name":"Firefox","major":"110"},"os":{"name":"Ubuntu"},"cpu":{"architecture":"amd64"}}"#,
+            "\n",
+            r#"{"product":{"name":"Other","major":null},"os":{"name":"Other"},"cpu":{"architecture":null}}"#,
+            "\n",
+        ),
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn test_csv_stdin() {
+    let path = regexes_path("csv");
+
+    let mut child = command()
+        .arg("--regexes")
+        .arg(&path)
+        .args(["--format", "csv", "--columns", "user_agent,product.name,product.minor"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(
+            format!("{FIREFOX}\r\n\nMozilla/5.0 (KHTML, like Gecko) Firefox/3.6\n").as_bytes(),
+        )
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    fs::remove_file(path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        format!(
+            "user_agent,product.name,product.minor\n{FIREFOX},Firefox,0\n\"Mozilla/5.0 (KHTML, \
+             like Gecko) Firefox/3.6\",Firefox,6\n"
+        ),
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn test_tsv() {
+    let path = regexes_path("tsv");
+
+    let output = command()
+        .arg("--regexes")
+        .arg(&path)
+        .args(["-f", "tsv", "-c", "product.name,os.name,os.major", FIREFOX])
+        .output()
+        .unwrap();
+
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(
+        "product.name\tos.name\tos.major\nFirefox\tUbuntu\t22\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
+
+#[test]
+fn test_errors() {
+    let output = command().args(["--columns", "product.version", FIREFOX]).output().unwrap();
+
+    assert!(!output.status.success());
+
+    let output =
+        command().args(["--regexes", "/nonexistent/regexes.yaml", FIREFOX]).output().unwrap();
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_analyze() {
+    let path = regexes_path("analyze");
+
+    let mut child = command()
+        .arg("analyze")
+        .arg("--regexes")
+        .arg(&path)
+        .args([
+            "--log-format",
+            "jsonl",
+            "--json-key",
+            "ua",
+            "--by",
+            "product,os",
+            "--output",
+            "json",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let log = [
+        format!(r#"{{"ua":"{FIREFOX}"}}"#),
+        format!(r#"{{"ua":"{FIREFOX}"}}"#),
+        String::from(r#"{"ua":"curl/8.0"}"#),
+        String::from("{}"),
+    ];
+
+    child.stdin.take().unwrap().write_all(log.join("\n").as_bytes()).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    fs::remove_file(path).unwrap();
+
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(4, json["requests"]);
+    assert_eq!(1, json["without_user_agent"]);
+    assert_eq!("product", json["reports"][0]["dimension"]);
+    assert_eq!("Firefox", json["reports"][0]["entries"][0]["name"]);
+    assert_eq!(2, json["reports"][0]["entries"][0]["count"]);
+    assert_eq!("Ubuntu", json["reports"][1]["entries"][0]["name"]);
+    assert_eq!("Other", json["reports"][1]["entries"][1]["name"]);
+    assert!(json["reports"].get(2).is_none());
+}
+
+#[test]
+fn test_lint() {
+    let path = regexes_path("lint");
+
+    let output = command().arg("lint").arg(&path).output().unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let corpus =
+        env::temp_dir().join(format!("user-agent-parser-cli-corpus-{}.txt", std::process::id()));
+
+    fs::write(&corpus, format!("{FIREFOX}\n")).unwrap();
+
+    let lint_path =
+        env::temp_dir().join(format!("user-agent-parser-cli-rules-{}.yaml", std::process::id()));
+
+    fs::write(&lint_path, format!("{REGEXES}  - regex: '(Ubuntu)'\n    os_v1_replacement: '$2'\n"))
+        .unwrap();
+
+    let output =
+        command().arg("lint").arg("--corpus").arg(&corpus).arg(&lint_path).output().unwrap();
+
+    fs::remove_file(path).unwrap();
+    fs::remove_file(corpus).unwrap();
+    fs::remove_file(lint_path).unwrap();
+
+    assert!(!output.status.success());
+    assert_eq!(
+        "os_parsers[1]: `os_v1_replacement` references `$2` but the regex has only 1 capture \
+         group(s)\nos_parsers[1]: shadowed by the earlier rule(s) 0 on all of its 1 match(es) in \
+         the corpus\n",
+        String::from_utf8(output.stdout).unwrap()
+    );
+}
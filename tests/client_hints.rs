@@ -0,0 +1,151 @@
+use user_agent_parser::{Brand, ClientHints, UserAgentParser};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Chrome)/(\d+)\.(\d+)\.(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Windows NT 10\.0)'
+    os_replacement: 'Windows'
+    os_v1_replacement: '10'
+  - regex: '(Android) (\d+)'
+device_parsers:
+  - regex: '; ([^;]+) Build/'
+    device_replacement: '$1'
+    model_replacement: '$1'
+  - regex: 'Android \d+; ([^;)]+)\)'
+    device_replacement: '$1'
+    model_replacement: '$1'
+"#;
+
+const WINDOWS_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+                                  (KHTML, like Gecko) Chrome/110.0.0.0 Safari/537.36";
+
+const ANDROID_USER_AGENT: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, \
+                                  like Gecko) Chrome/110.0.0.0 Mobile Safari/537.36";
+
+#[test]
+fn test_brands() {
+    let client_hints = ClientHints::from_headers([(
+        "sec-ch-ua",
+        r#""Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110""#,
+    )]);
+
+    assert_eq!(
+        vec![
+            Brand {
+                name: "Chromium".to_string(), version: Some("110".to_string())
+            },
+            Brand {
+                name: "Google Chrome".to_string(), version: Some("110".to_string())
+            },
+        ],
+        client_hints.brands()
+    );
+
+    let client_hints = ClientHints::from_headers([(
+        "Sec-CH-UA",
+        r#"" Not;A Brand";v="99", "Microsoft Edge";v="103", "Chromium";v="103""#,
+    )]);
+
+    let names: Vec<String> = client_hints.brands().into_iter().map(|brand| brand.name).collect();
+
+    assert_eq!(vec!["Microsoft Edge", "Chromium"], names);
+}
+
+#[test]
+fn test_from_headers() {
+    let client_hints = ClientHints::from_headers([
+        ("Accept", "*/*"),
+        ("Sec-CH-UA-Mobile", "?1"),
+        ("Sec-CH-UA-Platform", r#""Android""#),
+        ("Sec-CH-UA-Model", r#""""#),
+    ]);
+
+    assert!(!client_hints.is_empty());
+    assert_eq!(Some(true), client_hints.is_mobile());
+    assert_eq!(Some("Android".to_string()), client_hints.platform_name());
+    assert_eq!(None, client_hints.model_name());
+    assert_eq!(None, client_hints.arch_name());
+
+    assert!(ClientHints::from_headers([("Accept", "*/*")]).is_empty());
+}
+
+#[test]
+fn test_merge_windows() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let client_hints = ClientHints::from_headers([
+        ("Sec-CH-UA", r#""Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110""#),
+        (
+            "Sec-CH-UA-Full-Version-List",
+            r#""Chromium";v="110.0.5481.77", "Not A(Brand";v="24.0.0.0", "Google Chrome";v="110.0.5481.77""#,
+        ),
+        ("Sec-CH-UA-Platform", r#""Windows""#),
+        ("Sec-CH-UA-Platform-Version", r#""15.0.0""#),
+        ("Sec-CH-UA-Arch", r#""x86""#),
+        ("Sec-CH-UA-Bitness", r#""64""#),
+        ("Sec-CH-UA-Mobile", "?0"),
+    ]);
+
+    let parsed = ua_parser.parse_all(WINDOWS_USER_AGENT);
+
+    assert_eq!(Some("10"), parsed.os.major.as_deref());
+
+    let parsed = ua_parser.parse_all_with_client_hints(WINDOWS_USER_AGENT, &client_hints);
+
+    assert_eq!(Some("Chrome"), parsed.product.name.as_deref());
+    assert_eq!(Some("110"), parsed.product.major.as_deref());
+    assert_eq!(Some("0"), parsed.product.minor.as_deref());
+    assert_eq!(Some("5481"), parsed.product.patch.as_deref());
+    assert_eq!(Some("Windows"), parsed.os.name.as_deref());
+    assert_eq!(Some("11"), parsed.os.major.as_deref());
+    assert_eq!(None, parsed.os.minor.as_deref());
+    assert_eq!(Some("amd64"), parsed.cpu.architecture.as_deref());
+    assert_eq!(Some("Other"), parsed.device.name.as_deref());
+}
+
+#[test]
+fn test_merge_android() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let client_hints = ClientHints::from_headers([
+        ("Sec-CH-UA", r#""Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110""#),
+        ("Sec-CH-UA-Platform", r#""Android""#),
+        ("Sec-CH-UA-Platform-Version", r#""13.0.0""#),
+        ("Sec-CH-UA-Model", r#""SM-G991B""#),
+        ("Sec-CH-UA-Arch", r#""""#),
+        ("Sec-CH-UA-Mobile", "?1"),
+    ]);
+
+    let parsed = ua_parser.parse_all(ANDROID_USER_AGENT);
+
+    assert_eq!(Some("K"), parsed.device.name.as_deref());
+
+    let parsed = ua_parser.parse_all_with_client_hints(ANDROID_USER_AGENT, &client_hints);
+
+    // only the significant version is sent, which agrees with the user agent
+    assert_eq!(Some("Chrome"), parsed.product.name.as_deref());
+    assert_eq!(Some("110"), parsed.product.major.as_deref());
+    assert_eq!(Some("0"), parsed.product.minor.as_deref());
+    assert_eq!(Some("Android"), parsed.os.name.as_deref());
+    assert_eq!(Some("13"), parsed.os.major.as_deref());
+    assert_eq!(None, parsed.os.minor.as_deref());
+    assert_eq!(Some("SM-G991B"), parsed.device.name.as_deref());
+    assert_eq!(Some("SM-G991B"), parsed.device.model.as_deref());
+    assert_eq!(None, parsed.cpu.architecture.as_deref());
+}
+
+#[test]
+fn test_merge_generic_smartphone() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let client_hints = ClientHints::from_headers([("Sec-CH-UA-Mobile", "?1")]);
+
+    let mut device = ua_parser.parse_device("Mozilla/5.0 (Mobile)");
+
+    client_hints.merge_device(&mut device);
+
+    assert_eq!(Some("Generic Smartphone"), device.name.as_deref());
+    assert_eq!(Some("Generic"), device.brand.as_deref());
+    assert_eq!(Some("Smartphone"), device.model.as_deref());
+}
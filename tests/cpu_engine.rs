@@ -0,0 +1,91 @@
+use user_agent_parser::{RuleErrorKind, RuleSection, UserAgentParser, UserAgentParserError};
+
+const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) \
+                          Ladybird/1.0 Chrome/120.0.0.0 Safari/537.36";
+
+#[test]
+fn test_extend_built_in_rules() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+cpu_parsers:
+  - regex: '(x86_64)'
+    architecture_replacement: 'x86-64'
+engine_parsers:
+  - regex: '(Ladybird)/(\d+)\.(\d+)'
+    regex_flag: 'i'
+    engine_v3_replacement: '0'
+"#,
+    )
+    .unwrap();
+
+    let cpu = ua_parser.parse_cpu(USER_AGENT);
+
+    assert_eq!(Some("x86-64"), cpu.architecture.as_deref());
+
+    let engine = ua_parser.parse_engine(USER_AGENT);
+
+    assert_eq!(Some("Ladybird"), engine.name.as_deref());
+    assert_eq!(Some("1"), engine.major.as_deref());
+    assert_eq!(Some("0"), engine.minor.as_deref());
+    assert_eq!(Some("0"), engine.patch.as_deref());
+
+    // the built-in rules are still tried after the custom ones
+    let engine = ua_parser.parse_engine("Mozilla/5.0 (Windows NT 10.0; rv:109.0) Gecko/20100101");
+
+    assert_eq!(Some("Gecko"), engine.name.as_deref());
+}
+
+#[test]
+fn test_replace_built_in_rules() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+cpu_parsers:
+  built_in: false
+engine_parsers:
+  built_in: false
+  rules:
+    - regex: 'ArkWeb/(\d+)\.(\d+)'
+      engine_replacement: 'ArkWeb'
+      engine_v1_replacement: '$1'
+      engine_v2_replacement: '$2'
+"#,
+    )
+    .unwrap();
+
+    let cpu = ua_parser.parse_cpu(USER_AGENT);
+
+    assert_eq!(None, cpu.architecture);
+
+    let engine = ua_parser.parse_engine(USER_AGENT);
+
+    assert_eq!(None, engine.name);
+
+    let engine =
+        ua_parser.parse_engine("Mozilla/5.0 (Phone; OpenHarmony 4.0) ArkWeb/4.1.6.1 Mobile");
+
+    assert_eq!(Some("ArkWeb"), engine.name.as_deref());
+    assert_eq!(Some("4"), engine.major.as_deref());
+    assert_eq!(Some("1"), engine.minor.as_deref());
+}
+
+#[test]
+fn test_invalid_engine_rule() {
+    let err = match UserAgentParser::from_str(
+        r#"
+engine_parsers:
+  built_in: false
+  rules:
+    - regex: 'ArkWeb/(\d+)'
+    - engine_replacement: 'Ladybird'
+"#,
+    ) {
+        Err(UserAgentParserError::InvalidRule(err)) => err,
+        _ => panic!("the YAML data should be invalid"),
+    };
+
+    assert_eq!(RuleSection::Engine, err.section);
+    assert_eq!(Some(1), err.index);
+    assert_eq!(Some("regex"), err.key);
+    assert_eq!(Some((6, 7)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::MissingKey));
+}
@@ -0,0 +1,142 @@
+use user_agent_parser::{DeviceType, ParseDeviceTypeError, UserAgentParser};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Chrome)/(\d+)'
+os_parsers:
+  - regex: '(Android)[ /](\d+)'
+  - regex: '(iPhone|iPad).+OS (\d+)'
+    os_replacement: 'iOS'
+  - regex: '(Windows) NT (\d+)'
+device_parsers:
+  - regex: '(Googlebot)'
+    device_replacement: 'Spider'
+  - regex: '(iPhone)'
+    brand_replacement: 'Apple'
+device_type_parsers:
+  - regex: 'InHouseKiosk'
+    device_type: 'tv'
+"#;
+
+#[test]
+fn test_device_type() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let test_cases = [
+        (
+            DeviceType::Mobile,
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 16_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, \
+             like Gecko) Version/16.0 Mobile/15E148 Safari/604.1",
+        ),
+        (
+            DeviceType::Tablet,
+            "Mozilla/5.0 (iPad; CPU OS 16_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like \
+             Gecko) Version/16.0 Mobile/15E148 Safari/604.1",
+        ),
+        (
+            DeviceType::Mobile,
+            "Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) \
+             Chrome/110.0.0.0 Mobile Safari/537.36",
+        ),
+        (
+            DeviceType::Tablet,
+            "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) \
+             Chrome/110.0.0.0 Safari/537.36",
+        ),
+        (
+            DeviceType::Desktop,
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+             Chrome/110.0.0.0 Safari/537.36",
+        ),
+        (
+            DeviceType::TV,
+            "Mozilla/5.0 (SMART-TV; Linux; Tizen 6.0) AppleWebKit/537.36 (KHTML, like Gecko) \
+             SamsungBrowser/4.0 Chrome/76.0.3809.146 TV Safari/537.36",
+        ),
+        (
+            DeviceType::Console,
+            "Mozilla/5.0 (PlayStation; PlayStation 5/2.26) AppleWebKit/605.1.15 (KHTML, like \
+             Gecko) Version/13.0 Safari/605.1.15",
+        ),
+        (
+            DeviceType::Wearable,
+            "Mozilla/5.0 (Linux; Android 11; Wear OS) AppleWebKit/537.36 (KHTML, like Gecko) \
+             Chrome/110.0.0.0 Mobile Safari/537.36",
+        ),
+        (
+            DeviceType::Bot,
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        ),
+        (DeviceType::Bot, "Mozilla/5.0 (compatible; ExampleCrawler/1.0)"),
+        (DeviceType::Mobile, "Mozilla/5.0 (Linux; Android 13; Cubot X30) Mobile"),
+        (DeviceType::TV, "InHouseKiosk/2.0 (Windows NT 10.0)"),
+        (DeviceType::Other, "curl/7.64.1"),
+    ];
+
+    for (device_type, user_agent) in test_cases {
+        assert_eq!(device_type, ua_parser.parse_device_type(user_agent), "{user_agent}");
+    }
+
+    let user_agent = test_cases[0].1;
+    let parsed = ua_parser.parse_all(user_agent);
+
+    assert_eq!(
+        DeviceType::Mobile,
+        ua_parser.classify_device_type(user_agent, &parsed.device, &parsed.os)
+    );
+}
+
+#[test]
+fn test_replace_built_in_rules() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+device_type_parsers:
+  built_in: false
+  rules:
+    - regex: 'InHouseKiosk'
+      device_type: 'tv'
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(DeviceType::TV, ua_parser.parse_device_type("InHouseKiosk/2.0 (Windows NT 10.0)"));
+    assert_eq!(
+        DeviceType::Other,
+        ua_parser.parse_device_type(
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 16_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, \
+             like Gecko) Version/16.0 Mobile/15E148 Safari/604.1"
+        )
+    );
+    assert_eq!(
+        DeviceType::Other,
+        ua_parser.parse_device_type(
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+        )
+    );
+}
+
+#[test]
+fn test_incorrect_device_type() {
+    assert!(UserAgentParser::from_str(
+        r#"
+device_type_parsers:
+  - regex: 'Foo'
+    device_type: 'fridge'
+"#
+    )
+    .is_err());
+
+    assert!(UserAgentParser::from_str(
+        r#"
+device_type_parsers:
+  - device_type: 'mobile'
+"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_parse_device_type() {
+    assert_eq!(Ok(DeviceType::TV), "TV".parse());
+    assert_eq!(Err(ParseDeviceTypeError), "fridge".parse::<DeviceType>());
+}
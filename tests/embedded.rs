@@ -0,0 +1,23 @@
+#![cfg(feature = "embedded-regexes")]
+
+use user_agent_parser::{embedded_regexes_version, UserAgentParser};
+
+#[test]
+fn test_default() {
+    let ua_parser = UserAgentParser::default();
+
+    let product = ua_parser.parse_product(
+        "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/115.0",
+    );
+
+    assert_eq!(Some("Firefox"), product.name.as_deref());
+    assert_eq!(Some("115"), product.major.as_deref());
+}
+
+#[test]
+fn test_version() {
+    let version = embedded_regexes_version();
+
+    assert!(!version.is_empty());
+    assert!(version.split('.').all(|part| part.parse::<u32>().is_ok()));
+}
@@ -0,0 +1,110 @@
+use user_agent_parser::{
+    ParseRuleSectionError, RuleErrorKind, RuleSection, UserAgentParser, UserAgentParserError,
+};
+
+fn rule_error(yaml: &str) -> user_agent_parser::RuleError {
+    match UserAgentParser::from_str(yaml) {
+        Err(UserAgentParserError::InvalidRule(err)) => *err,
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => panic!("the YAML data should be invalid"),
+    }
+}
+
+#[test]
+fn test_missing_key() {
+    let err = rule_error(
+        r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)'
+os_parsers:
+  - regex: '(Windows) NT'
+  - os_replacement: 'Linux'
+"#,
+    );
+
+    assert_eq!(RuleSection::OS, err.section);
+    assert_eq!(Some(1), err.index);
+    assert_eq!(Some("regex"), err.key);
+    assert_eq!(None, err.regex);
+    assert_eq!(Some((6, 5)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::MissingKey));
+
+    assert_eq!("os_parsers[1].regex (line 6, column 5): the key is missing", err.to_string());
+}
+
+#[test]
+fn test_not_a_string() {
+    let err = rule_error(
+        r#"
+device_parsers:
+  - regex: '(iPhone)'
+    device_replacement: 'iPhone'
+  - regex: '(Pixel) (\d+)'
+    model_replacement:
+      - 'Pixel'
+"#,
+    );
+
+    assert_eq!(RuleSection::Device, err.section);
+    assert_eq!(Some(1), err.index);
+    assert_eq!(Some("model_replacement"), err.key);
+    assert_eq!(Some(r"(Pixel) (\d+)"), err.regex.as_deref());
+    assert_eq!(Some((6, 5)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::NotAString));
+}
+
+#[test]
+fn test_invalid_regex() {
+    let err = rule_error(
+        r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)'
+  - regex: '(Chrome/(\d+)'
+"#,
+    );
+
+    assert_eq!(RuleSection::Product, err.section);
+    assert_eq!(Some(1), err.index);
+    assert_eq!(Some("regex"), err.key);
+    assert_eq!(Some(r"(Chrome/(\d+)"), err.regex.as_deref());
+    assert_eq!(Some((4, 5)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::Regex(_)));
+}
+
+#[test]
+fn test_invalid_section() {
+    let err = rule_error(
+        r#"
+os_parsers:
+  regex: '(Windows) NT'
+"#,
+    );
+
+    assert_eq!(RuleSection::OS, err.section);
+    assert_eq!(None, err.index);
+    assert_eq!(Some((3, 3)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::NotASequence));
+}
+
+#[test]
+fn test_unknown_regex_flag() {
+    let err = rule_error(
+        r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)'
+    regex_flag: 'iq'
+"#,
+    );
+
+    assert_eq!(RuleSection::Product, err.section);
+    assert_eq!(Some(0), err.index);
+    assert_eq!(Some("regex_flag"), err.key);
+    assert_eq!(Some((4, 5)), err.position);
+    assert!(matches!(err.kind, RuleErrorKind::InvalidValue(ref flags) if flags == "iq"));
+}
+
+#[test]
+fn test_parse_rule_section() {
+    assert_eq!(Ok(RuleSection::OS), "os_parsers".parse());
+    assert_eq!(Err(ParseRuleSectionError), "browser_parsers".parse::<RuleSection>());
+}
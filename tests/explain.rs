@@ -0,0 +1,97 @@
+use user_agent_parser::UserAgentParser;
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+  - regex: 'Mozilla/(\d+)\.(\d+)'
+    family_replacement: 'Mozilla $1'
+os_parsers:
+  - regex: '(Ubuntu)'
+device_parsers:
+  - regex: '; (SM-[A-Z0-9]+)'
+    device_replacement: 'Samsung $1'
+    brand_replacement: 'Samsung'
+  - regex: '; (SM-[A-Z]+)'
+    device_replacement: '$1'
+    brand_replacement: 'Generic'
+"#;
+
+#[test]
+fn test_explain_product() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let explanation = ua_parser.explain_product("Mozilla/5.0 (X11; Ubuntu) Firefox/110.0");
+
+    assert_eq!(Some("Firefox"), explanation.result.name.as_deref());
+
+    let matched = explanation.matched.unwrap();
+
+    assert_eq!(0, matched.index);
+    assert_eq!(r"(Firefox)/(\d+)\.(\d+)", matched.regex);
+    assert_eq!(
+        vec![Some("Firefox/110.0"), Some("Firefox"), Some("110"), Some("0")],
+        matched.captures
+    );
+    assert_eq!(
+        vec![
+            ("name", None, 1, Some("Firefox")),
+            ("major", None, 2, Some("110")),
+            ("minor", None, 3, Some("0")),
+            ("patch", None, 4, None),
+        ],
+        matched
+            .fields
+            .iter()
+            .map(|f| (f.field, f.replacement, f.group, f.value.as_deref()))
+            .collect::<Vec<_>>()
+    );
+
+    assert_eq!(1, explanation.later_matches.len());
+    assert_eq!(1, explanation.later_matches[0].index);
+    assert_eq!(Some("Mozilla $1"), explanation.later_matches[0].fields[0].replacement);
+    assert_eq!(Some("Mozilla 5"), explanation.later_matches[0].fields[0].value.as_deref());
+}
+
+#[test]
+fn test_explain_device() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let user_agent = "Mozilla/5.0 (Linux; Android 13; SM-S918B) AppleWebKit/537.36";
+
+    let explanation = ua_parser.explain_device(user_agent);
+
+    assert_eq!(Some("Samsung"), explanation.result.brand.as_deref());
+
+    let matched = explanation.matched.unwrap();
+
+    assert_eq!(0, matched.index);
+    assert_eq!(Some("Samsung $1"), matched.fields[0].replacement);
+    assert_eq!(Some("Samsung SM-S918B"), matched.fields[0].value.as_deref());
+    assert_eq!(
+        ("model", None, Some("SM-S918B")),
+        (
+            matched.fields[2].field,
+            matched.fields[2].replacement,
+            matched.fields[2].value.as_deref()
+        )
+    );
+
+    assert_eq!(vec![1], explanation.later_matches.iter().map(|m| m.index).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_explain_no_match() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let explanation = ua_parser.explain_os("curl/8.0");
+
+    assert_eq!(Some("Other"), explanation.result.name.as_deref());
+    assert!(explanation.matched.is_none());
+    assert!(explanation.later_matches.is_empty());
+
+    // the built-in rules are explained too
+    let explanation = ua_parser.explain_cpu("Mozilla/5.0 (X11; Linux x86_64)");
+
+    assert_eq!(Some("amd64"), explanation.result.architecture.as_deref());
+    assert_eq!(Some("amd64"), explanation.matched.unwrap().fields[0].value.as_deref());
+}
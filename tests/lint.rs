@@ -0,0 +1,143 @@
+use user_agent_parser::{LintKind, RuleSection, UserAgentParser};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+    v3_replacement: '$3'
+  - regex: '(Firefox)/(\d+)'
+    v2_replacement: '$3'
+  - regex: '(Chrome)/(\d+)'
+  - regex: '(Chrome)/(\d+)'
+os_parsers:
+  - regex: '((?:\w+\s?)+) NT'
+  - regex: '(Ubuntu)[ /](\d+)\{2\}'
+device_parsers:
+  - regex: '; ([a-z0-9]+)+ Build'
+    device_replacement: '$1'
+    brand_replacement: 'Generic $2 $3 $2'
+  - regex: '(?:[a-z]{2,})+x'
+  - regex: '(?:[a-z]{2})+x'
+"#;
+
+#[test]
+fn test_lint_rules() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let issues = ua_parser.lint(Vec::<&str>::new());
+
+    assert_eq!(
+        vec![
+            (RuleSection::Product, 1, LintKind::InvalidCaptureReference {
+                key:    "v2_replacement",
+                group:  3,
+                groups: 2,
+            }),
+            (RuleSection::Product, 3, LintKind::DuplicateRegex {
+                earlier: 2
+            }),
+            (RuleSection::OS, 0, LintKind::NestedQuantifier),
+            (RuleSection::Device, 0, LintKind::InvalidCaptureReference {
+                key:    "brand_replacement",
+                group:  2,
+                groups: 1,
+            }),
+            (RuleSection::Device, 0, LintKind::InvalidCaptureReference {
+                key:    "brand_replacement",
+                group:  3,
+                groups: 1,
+            }),
+            (RuleSection::Device, 0, LintKind::NestedQuantifier),
+            (RuleSection::Device, 1, LintKind::NestedQuantifier),
+        ],
+        issues
+            .into_iter()
+            .map(|issue| (issue.section, issue.index, issue.kind))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_lint_shadowed() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let corpus = ["Mozilla/5.0 Firefox/110.0", "Mozilla/5.0 Firefox/3.6", "Mozilla/5.0 Firefox/4"];
+
+    let issues: Vec<_> = ua_parser
+        .lint(corpus)
+        .into_iter()
+        .filter(|issue| {
+            issue.section == RuleSection::Product && matches!(issue.kind, LintKind::Shadowed { .. })
+        })
+        .collect();
+
+    // the second rule wins for `Firefox/4`
+    assert!(issues.is_empty());
+
+    let issues: Vec<_> = ua_parser
+        .lint(&corpus[..2])
+        .into_iter()
+        .filter(|issue| {
+            issue.section == RuleSection::Product && matches!(issue.kind, LintKind::Shadowed { .. })
+        })
+        .collect();
+
+    assert_eq!(1, issues.len());
+    assert_eq!((RuleSection::Product, 1), (issues[0].section, issues[0].index));
+    assert_eq!(
+        LintKind::Shadowed {
+            by: vec![0], matches: 2
+        },
+        issues[0].kind
+    );
+    assert_eq!(
+        "user_agent_parsers[1]: shadowed by the earlier rule(s) 0 on all of its 2 match(es) in \
+         the corpus",
+        issues[0].to_string()
+    );
+}
+
+#[test]
+fn test_lint_extensible_sections() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+cpu_parsers:
+  - regex: '(x86_64)'
+  - regex: '(x86_64)'
+engine_parsers:
+  - regex: '(Ladybird)/\d+'
+    engine_v1_replacement: '$3'
+bot_parsers:
+  - regex: 'InternalProbe'
+    bot_category: 'monitoring'
+    name_replacement: 'Probe $1'
+"#,
+    )
+    .unwrap();
+
+    let issues = ua_parser.lint(["Mozilla/5.0 (X11; Linux x86_64) Ladybird/1.0"]);
+
+    assert_eq!(
+        vec![
+            (RuleSection::CPU, 1, LintKind::DuplicateRegex {
+                earlier: 0
+            }),
+            (RuleSection::CPU, 1, LintKind::Shadowed {
+                by: vec![0], matches: 1
+            }),
+            (RuleSection::Engine, 0, LintKind::InvalidCaptureReference {
+                key:    "engine_v1_replacement",
+                group:  3,
+                groups: 1,
+            }),
+            (RuleSection::Bot, 0, LintKind::InvalidCaptureReference {
+                key:    "name_replacement",
+                group:  1,
+                groups: 0,
+            }),
+        ],
+        issues
+            .into_iter()
+            .map(|issue| (issue.section, issue.index, issue.kind))
+            .collect::<Vec<_>>()
+    );
+}
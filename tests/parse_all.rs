@@ -0,0 +1,45 @@
+use user_agent_parser::UserAgentParser;
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Ubuntu)/(\d+)\.(\d+)'
+device_parsers:
+  - regex: '(X11)'
+    device_replacement: 'Desktop'
+    brand_replacement: 'Generic'
+"#;
+
+#[test]
+fn test_parse_all() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let user_agent = "Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 \
+                      Ubuntu/10.04 (lucid) Firefox/3.6.12";
+
+    let parsed = ua_parser.parse_all(user_agent).into_owned();
+
+    let product = ua_parser.parse_product(user_agent);
+    let os = ua_parser.parse_os(user_agent);
+    let device = ua_parser.parse_device(user_agent);
+    let cpu = ua_parser.parse_cpu(user_agent);
+    let engine = ua_parser.parse_engine(user_agent);
+
+    assert_eq!(product.name, parsed.product.name);
+    assert_eq!(product.major, parsed.product.major);
+    assert_eq!(product.minor, parsed.product.minor);
+    assert_eq!(os.name, parsed.os.name);
+    assert_eq!(os.major, parsed.os.major);
+    assert_eq!(device.name, parsed.device.name);
+    assert_eq!(device.brand, parsed.device.brand);
+    assert_eq!(cpu.architecture, parsed.cpu.architecture);
+    assert_eq!(engine.name, parsed.engine.name);
+    assert_eq!(engine.major, parsed.engine.major);
+
+    assert_eq!(Some("Firefox"), parsed.product.name.as_deref());
+    assert_eq!(Some("Ubuntu"), parsed.os.name.as_deref());
+    assert_eq!(Some("Desktop"), parsed.device.name.as_deref());
+    assert_eq!(Some("amd64"), parsed.cpu.architecture.as_deref());
+    assert_eq!(Some("Gecko"), parsed.engine.name.as_deref());
+}
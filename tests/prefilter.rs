@@ -0,0 +1,110 @@
+use user_agent_parser::UserAgentParser;
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Opera)/(\d+)\.(\d+)'
+  - regex: '^(\w+)/(\d+)(?=\.)'
+  - regex: '(Firefox|Minefield|Iceweasel)/(\d+)\.(\d+)'
+  - regex: '(?i)(curl)/(\d+)'
+    family_replacement: 'cURL'
+os_parsers:
+  - regex: '(Windows NT) (\d+)\.(\d+)'
+    os_replacement: 'Windows'
+  - regex: '\b(Linux)\b'
+device_parsers:
+  - regex: '; *(sm-[a-z0-9]+) build'
+    regex_flag: 'i'
+    device_replacement: 'Samsung $1'
+    brand_replacement: 'Samsung'
+    model_replacement: '$1'
+  - regex: 'Ü(ber)Phone'
+    regex_flag: 'i'
+    device_replacement: 'UberPhone'
+  - regex: '(.)'
+    device_replacement: 'Generic'
+cpu_parsers:
+  - regex: '(aarch64|arm64)'
+    regex_flag: 'i'
+    architecture_replacement: 'arm64'
+engine_parsers:
+  - regex: '(Servo)/(\d+)\.(\d+)'
+"#;
+
+#[test]
+fn test_first_match_wins() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    // the second rule cannot be prefiltered, but it must still win over the third one
+    let product = ua_parser.parse_product("Firefox/3.6 (X11; Linux x86_64)");
+
+    assert_eq!(Some("Firefox"), product.name.as_deref());
+    assert_eq!(Some("3"), product.major.as_deref());
+    assert_eq!(None, product.minor.as_deref());
+
+    let product = ua_parser.parse_product("Mozilla/5.0 (X11; Linux x86_64) Iceweasel/10.0");
+
+    assert_eq!(Some("Mozilla"), product.name.as_deref());
+    assert_eq!(Some("5"), product.major.as_deref());
+
+    let product = ua_parser.parse_product("(X11) Minefield/3.7");
+
+    assert_eq!(Some("Minefield"), product.name.as_deref());
+    assert_eq!(Some("7"), product.minor.as_deref());
+
+    let product = ua_parser.parse_product("(libcurl) CURL/7.64.1");
+
+    assert_eq!(Some("cURL"), product.name.as_deref());
+
+    let product = ua_parser.parse_product("(unknown)");
+
+    assert_eq!(Some("Other"), product.name.as_deref());
+}
+
+#[test]
+fn test_sections() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let user_agent =
+        "Mozilla/5.0 (Linux; Android 11; SM-G991B Build/RP1A.200720.012) Opera/9.80 Windows NT 6.1";
+
+    let parsed = ua_parser.parse_all(user_agent);
+
+    assert_eq!(Some("Opera"), parsed.product.name.as_deref());
+    assert_eq!(Some("Windows"), parsed.os.name.as_deref());
+    assert_eq!(Some("6"), parsed.os.major.as_deref());
+    assert_eq!(Some("Samsung SM-G991B"), parsed.device.name.as_deref());
+    assert_eq!(Some("SM-G991B"), parsed.device.model.as_deref());
+
+    assert_eq!(
+        Some("Linux"),
+        ua_parser.parse_os("Mozilla/5.0 (X11; LINUX; Linux)").name.as_deref()
+    );
+    assert_eq!(Some("Other"), ua_parser.parse_os("Mozilla/5.0 (X11; LINUX)").name.as_deref());
+
+    assert_eq!(Some("UberPhone"), ua_parser.parse_device("üBERPHONE/1.0").name.as_deref());
+    assert_eq!(Some("Generic"), ua_parser.parse_device("Mozilla/5.0").name.as_deref());
+    assert_eq!(Some("Other"), ua_parser.parse_device("").name.as_deref());
+}
+
+#[test]
+fn test_cpu_engine_sections() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let user_agent = "Mozilla/5.0 (X11; Linux AArch64; rv:120.0) Servo/1.0 Firefox/120.0";
+
+    let parsed = ua_parser.parse_all(user_agent);
+
+    assert_eq!(Some("arm64"), parsed.cpu.architecture.as_deref());
+    assert_eq!(Some("Servo"), parsed.engine.name.as_deref());
+    assert_eq!(Some("1"), parsed.engine.major.as_deref());
+
+    // the built-in rules are still tried after the custom ones
+    let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:120.0) Gecko/20100101";
+
+    let parsed = ua_parser.parse_all(user_agent);
+
+    assert_eq!(Some("amd64"), parsed.cpu.architecture.as_deref());
+    assert_eq!(Some("Gecko"), parsed.engine.name.as_deref());
+    assert_eq!(parsed.cpu.architecture, ua_parser.parse_cpu(user_agent).architecture);
+    assert_eq!(parsed.engine.name, ua_parser.parse_engine(user_agent).name);
+}
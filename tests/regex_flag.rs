@@ -0,0 +1,63 @@
+use user_agent_parser::UserAgentParser;
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(firefox)/(\d+)'
+    regex_flag: 'i'
+    family_replacement: 'Firefox'
+  - regex: '(Chrome) / (\d+) # the product and the major version'
+    regex_flag: 'x'
+os_parsers:
+  - regex: '^(windows) nt (\d+)$'
+    regex_flag: 'im'
+    os_replacement: 'Windows'
+device_parsers:
+  - regex: '(pixel) (\d+)'
+    regex_flag: 'i'
+    device_replacement: 'Pixel $2'
+"#;
+
+#[test]
+fn test_regex_flag() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let product = ua_parser.parse_product("Mozilla/5.0 FIREFOX/110");
+
+    assert_eq!(Some("Firefox"), product.name.as_deref());
+    assert_eq!(Some("110"), product.major.as_deref());
+
+    let product = ua_parser.parse_product("Mozilla/5.0 Chrome/112");
+
+    assert_eq!(Some("Chrome"), product.name.as_deref());
+    assert_eq!(Some("112"), product.major.as_deref());
+
+    let os = ua_parser.parse_os("Mozilla/5.0\nWINDOWS NT 10\n");
+
+    assert_eq!(Some("Windows"), os.name.as_deref());
+    assert_eq!(Some("10"), os.major.as_deref());
+
+    let device = ua_parser.parse_device("Mozilla/5.0 (Linux; Android 13; PIXEL 7)");
+
+    assert_eq!(Some("Pixel 7"), device.name.as_deref());
+}
+
+#[test]
+fn test_multi_line_flag() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+user_agent_parsers:
+  - regex: '^(Bar)/(\d+)$'
+    regex_flag: 'm'
+os_parsers:
+  - regex: '^(Bar)/(\d+)$'
+"#,
+    )
+    .unwrap();
+
+    let user_agent = "x\nBar/1\ny";
+
+    assert_eq!(Some("Bar"), ua_parser.parse_product(user_agent).name.as_deref());
+    assert_eq!(Some("Other"), ua_parser.parse_os(user_agent).name.as_deref());
+
+    assert_eq!(Some("Bar"), ua_parser.parse_os("Bar/1").name.as_deref());
+}
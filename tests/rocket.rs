@@ -0,0 +1,140 @@
+#![cfg(feature = "rocket")]
+
+use std::{borrow::Cow, env, fs};
+
+use rocket::{
+    error::ErrorKind, get, http::Status, local::asynchronous::Client, routes, Build, Rocket,
+};
+use user_agent_parser::{
+    Engine, ParsedUserAgent, Product, UserAgentParser, UserAgentParserFairing, OS,
+};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Linux)'
+"#;
+
+const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:10.0) Gecko/20100101 Firefox/10.0";
+
+#[get("/")]
+fn index(product: Product) -> String {
+    product.name.unwrap_or_default().into_owned()
+}
+
+#[get("/all")]
+fn all(parsed: &ParsedUserAgent, product: &Product, os: OS, engine: Engine) -> String {
+    assert_eq!(parsed.product.name, product.name);
+    assert_eq!(parsed.os.name, os.name);
+    assert!(matches!(os.name, Some(Cow::Borrowed(_))));
+
+    format!(
+        "{} {} {}",
+        product.name.as_deref().unwrap_or_default(),
+        os.name.as_deref().unwrap_or_default(),
+        engine.name.as_deref().unwrap_or_default()
+    )
+}
+
+fn rocket(config: &[(&str, &str)]) -> Rocket<Build> {
+    let mut figment = rocket::Config::figment().merge(("log_level", "off"));
+
+    for &(key, value) in config {
+        figment = figment.merge((key, value));
+    }
+
+    rocket::custom(figment).mount("/", routes![index, all])
+}
+
+#[rocket::async_test]
+async fn test_fairing_path() {
+    let path =
+        env::temp_dir().join(format!("user-agent-parser-rocket-{}.yaml", std::process::id()));
+
+    fs::write(&path, REGEXES).unwrap();
+
+    let client = Client::tracked(
+        rocket(&[("user_agent_parser.path", path.to_str().unwrap())])
+            .attach(UserAgentParserFairing),
+    )
+    .await
+    .unwrap();
+
+    let response = client
+        .get("/")
+        .header(rocket::http::Header::new("User-Agent", USER_AGENT))
+        .dispatch()
+        .await;
+
+    assert_eq!(Status::Ok, response.status());
+    assert_eq!("Firefox", response.into_string().await.unwrap());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[rocket::async_test]
+async fn test_fairing_managed() {
+    let client = Client::tracked(
+        rocket(&[("user_agent_parser.path", "/nonexistent/regexes.yaml")])
+            .manage(UserAgentParser::from_str(REGEXES).unwrap())
+            .attach(UserAgentParserFairing),
+    )
+    .await
+    .unwrap();
+
+    let response = client
+        .get("/")
+        .header(rocket::http::Header::new("User-Agent", USER_AGENT))
+        .dispatch()
+        .await;
+
+    assert_eq!("Firefox", response.into_string().await.unwrap());
+}
+
+#[rocket::async_test]
+async fn test_fairing_incorrect_path() {
+    let error = Client::tracked(
+        rocket(&[("user_agent_parser.path", "/nonexistent/regexes.yaml")])
+            .attach(UserAgentParserFairing),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(error.kind(), ErrorKind::FailedFairings(_)));
+
+    #[cfg(not(feature = "embedded-regexes"))]
+    {
+        let error = Client::tracked(rocket(&[]).attach(UserAgentParserFairing)).await.unwrap_err();
+
+        assert!(matches!(error.kind(), ErrorKind::FailedFairings(_)));
+    }
+}
+
+#[rocket::async_test]
+async fn test_missing_parser() {
+    let client = Client::tracked(rocket(&[])).await.unwrap();
+
+    let response = client
+        .get("/")
+        .header(rocket::http::Header::new("User-Agent", USER_AGENT))
+        .dispatch()
+        .await;
+
+    assert_eq!(Status::InternalServerError, response.status());
+}
+
+#[rocket::async_test]
+async fn test_parsed_user_agent() {
+    let client = Client::tracked(rocket(&[]).manage(UserAgentParser::from_str(REGEXES).unwrap()))
+        .await
+        .unwrap();
+
+    let response = client
+        .get("/all")
+        .header(rocket::http::Header::new("User-Agent", USER_AGENT))
+        .dispatch()
+        .await;
+
+    assert_eq!("Firefox Linux Gecko", response.into_string().await.unwrap());
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use user_agent_parser::{DeviceType, ParsedUserAgent, Product, UserAgentParser, Version, CPU};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Ubuntu)/(\d+)\.(\d+)'
+device_parsers:
+  - regex: '(X11)'
+    device_replacement: 'Desktop'
+    brand_replacement: 'Generic'
+"#;
+
+#[test]
+fn test_field_names() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let parsed = ua_parser.parse_all(
+        "Mozilla/5.0 (X11; U; Linux x86_64; en-US; rv:1.9.2.12) Gecko/20101027 Ubuntu/10.04 \
+         (lucid) Firefox/3.6.12",
+    );
+
+    assert_eq!(
+        json!({
+            "product": { "name": "Firefox", "major": "3", "minor": "6", "patch": null },
+            "os": { "name": "Ubuntu", "major": "10", "minor": "04", "patch": null, "patch_minor": null },
+            "device": { "name": "Desktop", "brand": "Generic", "model": "X11" },
+            "cpu": { "architecture": "amd64" },
+            "engine": { "name": "Gecko", "major": "1", "minor": "9", "patch": "2" },
+        }),
+        serde_json::to_value(&parsed).unwrap()
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let ua_parser = UserAgentParser::from_str(REGEXES).unwrap();
+
+    let parsed = ua_parser.parse_all("Mozilla/5.0 (X11; Ubuntu/22.04) Firefox/110.0");
+
+    let json = serde_json::to_string(&parsed).unwrap();
+
+    let deserialized: ParsedUserAgent<'static> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed.product.name, deserialized.product.name);
+    assert_eq!(parsed.product.major, deserialized.product.major);
+    assert_eq!(parsed.os.name, deserialized.os.name);
+    assert_eq!(parsed.device.brand, deserialized.device.brand);
+    assert_eq!(parsed.cpu.architecture, deserialized.cpu.architecture);
+
+    // missing fields are `None`
+    let product: Product<'static> = serde_json::from_str(r#"{ "name": "Chrome" }"#).unwrap();
+
+    assert_eq!(Some("Chrome"), product.name.as_deref());
+    assert_eq!(None, product.major);
+
+    let cpu: CPU<'static> = serde_json::from_value(json!({})).unwrap();
+
+    assert_eq!(None, cpu.architecture);
+}
+
+#[test]
+fn test_version_and_device_type() {
+    let version: Version = "110.0.5481.77".parse().unwrap();
+
+    assert_eq!(json!("110.0.5481.77"), serde_json::to_value(&version).unwrap());
+    assert_eq!(version, serde_json::from_value::<Version>(json!("110.0.5481.77")).unwrap());
+    assert_eq!(json!(""), serde_json::to_value(Version::default()).unwrap());
+    assert!(serde_json::from_value::<Version>(json!("")).unwrap().is_empty());
+
+    assert_eq!(json!("tv"), serde_json::to_value(DeviceType::TV).unwrap());
+    assert_eq!(DeviceType::Mobile, serde_json::from_value::<DeviceType>(json!("mobile")).unwrap());
+    assert!(serde_json::from_value::<DeviceType>(json!("phablet")).is_err());
+}
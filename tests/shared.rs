@@ -0,0 +1,82 @@
+use std::{env, fs, path::PathBuf};
+
+use user_agent_parser::{SharedUserAgentParser, UserAgentParser};
+
+const CHROME: &str = r#"
+user_agent_parsers:
+  - regex: '(Chrome)/(\d+)'
+"#;
+
+const FIREFOX: &str = r#"
+user_agent_parsers:
+  - regex: '(Firefox)/(\d+)'
+"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("user-agent-parser-shared-{name}-{}.yaml", std::process::id()))
+}
+
+#[test]
+fn test_reload_from_path() {
+    let path = temp_path("reload");
+
+    let shared = SharedUserAgentParser::new(UserAgentParser::from_str(CHROME).unwrap());
+    let cloned = shared.clone();
+
+    let snapshot = shared.load();
+
+    fs::write(&path, FIREFOX).unwrap();
+
+    shared.reload_from_path(&path).unwrap();
+
+    // the snapshot taken before the reload keeps the old rules
+    assert_eq!(Some("Chrome"), snapshot.parse_product("Chrome/110").name.as_deref());
+
+    // all clones see the new rules
+    assert_eq!(Some("Firefox"), cloned.load().parse_product("Firefox/110").name.as_deref());
+    assert_eq!(Some("Other"), cloned.load().parse_product("Chrome/110").name.as_deref());
+
+    // an invalid file keeps the current parser
+    fs::write(&path, "user_agent_parsers:\n  - family_replacement: 'Chrome'\n").unwrap();
+
+    assert!(shared.reload_from_path(&path).is_err());
+    assert_eq!(Some("Firefox"), shared.load().parse_product("Firefox/110").name.as_deref());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn test_watch() {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    let path = temp_path("watch");
+
+    fs::write(&path, CHROME).unwrap();
+
+    let shared = SharedUserAgentParser::new(UserAgentParser::from_path(&path).unwrap());
+
+    let errors = Arc::new(Mutex::new(0));
+
+    let _watcher = {
+        let errors = errors.clone();
+
+        shared.watch(&path, move |_| *errors.lock().unwrap() += 1).unwrap()
+    };
+
+    fs::write(&path, FIREFOX).unwrap();
+
+    let start = Instant::now();
+
+    while shared.load().parse_product("Firefox/110").name.as_deref() != Some("Firefox") {
+        assert!(start.elapsed() < Duration::from_secs(10), "the file has not been reloaded");
+
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    fs::remove_file(path).unwrap();
+}
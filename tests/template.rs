@@ -0,0 +1,127 @@
+use user_agent_parser::{RuleErrorKind, UserAgentParser, UserAgentParserError};
+
+#[test]
+fn test_multi_digit_references() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+user_agent_parsers:
+  - regex: '(a)(b)(c)(d)(e)(f)(g)(h)(i)(j)(k)(l)'
+    family_replacement: '$12$10 ${1}0'
+    v1_replacement: '$0'
+"#,
+    )
+    .unwrap();
+
+    let product = ua_parser.parse_product("abcdefghijkl");
+
+    assert_eq!(Some("lj a0"), product.name.as_deref());
+    assert_eq!(Some("abcdefghijkl"), product.major.as_deref());
+}
+
+#[test]
+fn test_named_references() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+user_agent_parsers:
+  - regex: '(?<browser>Ladybird)/(?<major>\d+)'
+    family_replacement: '${browser} $$'
+    v1_replacement: '${major}'
+    v2_replacement: '$'
+"#,
+    )
+    .unwrap();
+
+    let product = ua_parser.parse_product("Mozilla/5.0 Ladybird/1");
+
+    assert_eq!(Some("Ladybird $"), product.name.as_deref());
+    assert_eq!(Some("1"), product.major.as_deref());
+    assert_eq!(Some("$"), product.minor.as_deref());
+}
+
+#[test]
+fn test_mixed_references() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+user_agent_parsers:
+  - regex: '(?<browser>Foo) (\d+)\.(\d+)'
+    family_replacement: '${browser} $2'
+    v1_replacement: '$2'
+    v2_replacement: '${3}'
+"#,
+    )
+    .unwrap();
+
+    let product = ua_parser.parse_product("Mozilla/5.0 Foo 12.3");
+
+    assert_eq!(Some("Foo 12"), product.name.as_deref());
+    assert_eq!(Some("12"), product.major.as_deref());
+    assert_eq!(Some("3"), product.minor.as_deref());
+}
+
+#[test]
+fn test_unknown_capture_name() {
+    let err = match UserAgentParser::from_str(
+        r#"
+os_parsers:
+  - regex: '(?<os>Windows) NT'
+    os_replacement: '${name}'
+"#,
+    ) {
+        Err(UserAgentParserError::InvalidRule(err)) => err,
+        _ => panic!("the YAML data should be invalid"),
+    };
+
+    assert_eq!(Some(0), err.index);
+    assert_eq!(Some("os_replacement"), err.key);
+    assert!(matches!(err.kind, RuleErrorKind::UnknownCaptureName(ref name) if name == "name"));
+}
+
+#[test]
+fn test_transforms() {
+    let ua_parser = UserAgentParser::from_str(
+        r#"
+user_agent_parsers:
+  - regex: '(ladybird)/([\d_]+)'
+    family_replacement: '${1:upper}'
+    v1_replacement: '${2:dots}'
+device_parsers:
+  - regex: '; (\w+) (SM_\w+) Build/'
+    regex_flag: 'i'
+    device_replacement: '${1:title} ${2:upper}'
+    brand_replacement: '${1:lower:title}'
+    model_replacement: '${2:upper:strip_suffix=_TD}'
+"#,
+    )
+    .unwrap();
+
+    let product = ua_parser.parse_product("Mozilla/5.0 ladybird/1_0");
+
+    assert_eq!(Some("LADYBIRD"), product.name.as_deref());
+    assert_eq!(Some("1.0"), product.major.as_deref());
+
+    let device =
+        ua_parser.parse_device("Mozilla/5.0 (Linux; Android 13; SAMSUNG sm_g991b_td Build/TP1A)");
+
+    assert_eq!(Some("Samsung SM_G991B_TD"), device.name.as_deref());
+    assert_eq!(Some("Samsung"), device.brand.as_deref());
+    assert_eq!(Some("SM_G991B"), device.model.as_deref());
+}
+
+#[test]
+fn test_unknown_transform() {
+    let err = match UserAgentParser::from_str(
+        r#"
+device_parsers:
+  - regex: '(Pixel) (\d+)'
+    brand_replacement: '${1:capitalize}'
+"#,
+    ) {
+        Err(UserAgentParserError::InvalidRule(err)) => err,
+        _ => panic!("the YAML data should be invalid"),
+    };
+
+    assert_eq!(Some("brand_replacement"), err.key);
+    assert!(
+        matches!(err.kind, RuleErrorKind::UnknownTransform(ref transform) if transform == "capitalize")
+    );
+}
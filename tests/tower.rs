@@ -0,0 +1,93 @@
+#![cfg(feature = "tower")]
+
+use std::{convert::Infallible, sync::Arc};
+
+use axum::http::Request;
+use tower::{service_fn, Layer, ServiceExt};
+use user_agent_parser::{ParsedUserAgent, UserAgentLayer, UserAgentParser};
+
+const REGEXES: &str = r#"
+user_agent_parsers:
+  - regex: '(Chrome)/(\d+)\.(\d+)\.(\d+)\.(\d+)'
+os_parsers:
+  - regex: '(Windows NT 10\.0)'
+    os_replacement: 'Windows'
+    os_v1_replacement: '10'
+"#;
+
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, \
+                          like Gecko) Chrome/110.0.0.0 Safari/537.36";
+
+async fn call(layer: UserAgentLayer, req: Request<()>) -> ParsedUserAgent<'static> {
+    let service = layer.layer(service_fn(|req: Request<()>| async move {
+        Ok::<_, Infallible>(req.extensions().get::<ParsedUserAgent<'static>>().cloned())
+    }));
+
+    service.oneshot(req).await.unwrap().unwrap()
+}
+
+fn request() -> Request<()> {
+    Request::builder()
+        .header("user-agent", USER_AGENT)
+        .header("sec-ch-ua-platform", r#""Windows""#)
+        .header("sec-ch-ua-platform-version", r#""15.0.0""#)
+        .body(())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_layer() {
+    let user_agent_parser = Arc::new(UserAgentParser::from_str(REGEXES).unwrap());
+
+    let parsed = call(UserAgentLayer::new(user_agent_parser), request()).await;
+
+    assert_eq!(Some("Chrome"), parsed.product.name.as_deref());
+    assert_eq!(Some("10"), parsed.os.major.as_deref());
+    assert_eq!(Some("amd64"), parsed.cpu.architecture.as_deref());
+}
+
+#[tokio::test]
+async fn test_options() {
+    let user_agent_parser = Arc::new(UserAgentParser::from_str(REGEXES).unwrap());
+
+    let layer = UserAgentLayer::new(user_agent_parser).product(false).cpu(false).client_hints(true);
+
+    let parsed = call(layer, request()).await;
+
+    assert_eq!(None, parsed.product.name);
+    assert_eq!(None, parsed.cpu.architecture);
+    assert_eq!(Some("Windows"), parsed.os.name.as_deref());
+    assert_eq!(Some("11"), parsed.os.major.as_deref());
+    assert_eq!(Some("Blink"), parsed.engine.name.as_deref());
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn test_extractor_behind_layer() {
+    use axum::extract::FromRequestParts;
+    use user_agent_parser::Product;
+
+    let user_agent_parser = Arc::new(UserAgentParser::from_str(REGEXES).unwrap());
+
+    let layer = UserAgentLayer::new(user_agent_parser.clone()).product(false);
+
+    let service = layer.layer(service_fn(|req: Request<()>| async move {
+        let (mut parts, _) = req.into_parts();
+
+        let product = Product::from_request_parts(&mut parts, &()).await.unwrap();
+        let parsed = ParsedUserAgent::from_request_parts(&mut parts, &()).await.unwrap();
+
+        Ok::<_, Infallible>((product, parsed))
+    }));
+
+    let mut req = request();
+
+    req.extensions_mut().insert(user_agent_parser);
+
+    let (product, parsed) = service.oneshot(req).await.unwrap();
+
+    // the product section skipped by the layer is parsed by the extractor
+    assert_eq!(Some("Chrome"), product.name.as_deref());
+    assert_eq!(Some("Chrome"), parsed.product.name.as_deref());
+    assert_eq!(Some("Windows"), parsed.os.name.as_deref());
+}
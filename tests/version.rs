@@ -0,0 +1,77 @@
+use std::{borrow::Cow, collections::HashSet};
+
+use user_agent_parser::{Engine, Product, Version, OS};
+
+#[test]
+fn test_parse() {
+    let version: Version = "110.0.5481.77".parse().unwrap();
+
+    assert_eq!(4, version.len());
+    assert_eq!(Some(110), version.major());
+    assert_eq!(Some(0), version.minor());
+    assert_eq!(Some(5481), version.patch());
+    assert_eq!(Some("77"), version.get(3));
+    assert_eq!("110.0.5481.77", version.to_string());
+
+    let version: Version = "4.0b9pre".parse().unwrap();
+
+    assert_eq!(Some(0), version.minor());
+    assert_eq!(Some("0b9pre"), version.get(1));
+    assert_eq!("4.0b9pre", version.to_string());
+
+    assert!("".parse::<Version>().is_err());
+    assert!("1..2".parse::<Version>().is_err());
+}
+
+#[test]
+fn test_compare() {
+    let v = |s: &str| s.parse::<Version>().unwrap();
+
+    assert!(v("110.0.5481.77") > v("110.0"));
+    assert!(v("110.0.5481.77") < v("111"));
+    assert!(v("9.1") < v("10.0"));
+    assert_eq!(v("110"), v("110.0.0"));
+    assert!(v("4.0b9pre") < v("4.0"));
+    assert!(v("4.0b2") < v("4.0b9"));
+    assert!(v("1.beta") < v("1.0"));
+    assert!(Version::default() < v("0"));
+    assert_ne!(Version::default(), v("0"));
+
+    let set: HashSet<Version> = [v("110"), v("110.0"), v("110.0.0"), v("110.1")].into();
+
+    assert_eq!(2, set.len());
+
+    assert!(v("110.0.5481.77") >= "110.0");
+    assert!(v("110.0.5481.77") == "110.0.5481.77.0");
+    assert_eq!(None, v("110.0").partial_cmp("not..a version"));
+}
+
+#[test]
+fn test_models() {
+    let product = Product {
+        name:  Some(Cow::from("Chrome")),
+        major: Some(Cow::from("110")),
+        minor: Some(Cow::from("0")),
+        patch: Some(Cow::from("5481")),
+    };
+
+    assert!(product.version() >= "110.0");
+    assert!(product.version() < "110.0.5482");
+
+    let os = OS {
+        name: Some(Cow::from("Windows")),
+        major: Some(Cow::from("10")),
+        ..OS::default()
+    };
+
+    assert_eq!(os.version(), "10");
+
+    let engine = Engine {
+        name: Some(Cow::from("Gecko")),
+        major: None,
+        minor: Some(Cow::from("0")),
+        ..Engine::default()
+    };
+
+    assert!(engine.version().is_empty());
+}